@@ -0,0 +1,197 @@
+use core::{
+    fmt::{self, Display},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign},
+};
+
+pub use num_bigint::BigUint;
+
+/// A non negative puzzle answer that can't overflow.
+///
+/// It's a plain `u128` until a sum or product doesn't fit, and a big integer from then on.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Answer(Repr);
+
+/// `Big` only ever holds values above `u128::MAX`, so every value has a single representation
+/// and the derived comparisons hold
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Repr {
+    Small(u128),
+    Big(BigUint),
+}
+
+impl Answer {
+    pub const ZERO: Self = Self(Repr::Small(0));
+    pub const ONE: Self = Self(Repr::Small(1));
+
+    pub fn is_big(&self) -> bool {
+        matches!(self.0, Repr::Big(_))
+    }
+
+    fn to_biguint(&self) -> BigUint {
+        match &self.0 {
+            Repr::Small(x) => BigUint::from(*x),
+            Repr::Big(x) => x.clone(),
+        }
+    }
+
+    fn from_biguint(x: BigUint) -> Self {
+        match u128::try_from(&x) {
+            Ok(small) => Self(Repr::Small(small)),
+            Err(_) => Self(Repr::Big(x)),
+        }
+    }
+}
+
+impl Default for Answer {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Repr::Small(x) => x.fmt(f),
+            Repr::Big(x) => x.fmt(f),
+        }
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(x: $t) -> Self {
+                    Self(Repr::Small(x as u128))
+                }
+            }
+
+            impl TryFrom<Answer> for $t {
+                type Error = Answer;
+
+                /// Gives the answer back if it doesn't fit
+                fn try_from(answer: Answer) -> Result<Self, Self::Error> {
+                    match answer.0 {
+                        Repr::Small(x) => <$t>::try_from(x).map_err(|_| answer),
+                        Repr::Big(_) => Err(answer),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+// only for `u64`, so comparing with an integer literal infers its type
+impl PartialEq<u64> for Answer {
+    fn eq(&self, other: &u64) -> bool {
+        matches!(self.0, Repr::Small(x) if x == u128::from(*other))
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(x: BigUint) -> Self {
+        Self::from_biguint(x)
+    }
+}
+
+impl From<Answer> for BigUint {
+    fn from(answer: Answer) -> Self {
+        match answer.0 {
+            Repr::Small(x) => BigUint::from(x),
+            Repr::Big(x) => x,
+        }
+    }
+}
+
+impl Add for Answer {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if let (Repr::Small(a), Repr::Small(b)) = (&self.0, &rhs.0)
+            && let Some(sum) = a.checked_add(*b)
+        {
+            return Self(Repr::Small(sum));
+        }
+        Self(Repr::Big(self.to_biguint() + rhs.to_biguint()))
+    }
+}
+
+impl Mul for Answer {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if let (Repr::Small(a), Repr::Small(b)) = (&self.0, &rhs.0) {
+            if let Some(product) = a.checked_mul(*b) {
+                return Self(Repr::Small(product));
+            }
+            return Self(Repr::Big(BigUint::from(*a) * *b));
+        }
+        // a product with zero shrinks back to a small value
+        Self::from_biguint(self.to_biguint() * rhs.to_biguint())
+    }
+}
+
+impl AddAssign for Answer {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = core::mem::take(self) + rhs;
+    }
+}
+
+impl MulAssign for Answer {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = core::mem::take(self) * rhs;
+    }
+}
+
+impl Sum for Answer {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Product for Answer {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_promotes_to_big() {
+        let max = Answer::from(u128::MAX);
+        assert!(!max.is_big());
+
+        let sum = max.clone() + Answer::ONE;
+        assert!(sum.is_big());
+        assert_eq!(sum.to_string(), "340282366920938463463374607431768211456");
+        assert!(sum > max);
+
+        let product: Answer = [u64::MAX; 3].into_iter().map(Answer::from).product();
+        assert_eq!(
+            product.to_string(),
+            "6277101735386680762814942322444851025767571854389858533375"
+        );
+        assert_eq!(u64::try_from(product.clone()), Err(product.clone()));
+        assert_eq!(product * Answer::ZERO, 0);
+    }
+
+    #[test]
+    fn test_small() {
+        let mut answer: Answer = [3u32, 4, 5].into_iter().map(Answer::from).sum();
+        answer *= Answer::from(2u8);
+        assert_eq!(answer, 24);
+        assert_eq!(u16::try_from(answer), Ok(24));
+        assert_eq!(
+            u8::try_from(Answer::from(300u16)),
+            Err(Answer::from(300u16))
+        );
+    }
+}
@@ -0,0 +1,1137 @@
+use alloc::{
+    collections::{BTreeMap, TryReserveError},
+    vec,
+    vec::Vec,
+};
+use core::{
+    cmp::Reverse,
+    fmt::{self, Display},
+    num::ParseIntError,
+    str::FromStr,
+};
+
+use crate::{interval_set::IntervalSet, range::InclusiveRange};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Direction {
+    /// Towards lower numbers
+    Left,
+    /// Towards higher numbers
+    Right,
+}
+
+impl Direction {
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRotationError {
+    Empty,
+    InvalidDirection(char),
+    MissingDistance,
+    InvalidDistance(ParseIntError),
+    DistanceTooLarge(u64),
+}
+
+impl Display for ParseRotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "expected L<distance> or R<distance>"),
+            Self::InvalidDirection(c) => write!(f, "expected 'L' or 'R', found '{c}'"),
+            Self::MissingDistance => write!(f, "expected a distance after the direction"),
+            Self::InvalidDistance(e) => write!(f, "failed to parse distance: {e}"),
+            Self::DistanceTooLarge(distance) => {
+                write!(
+                    f,
+                    "distance {distance} is too large, it can be at most {}",
+                    i64::MAX
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseRotationError {}
+
+/// Turning the dial a number of clicks in one direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rotation {
+    pub direction: Direction,
+    pub distance: u64,
+}
+
+impl Rotation {
+    pub const fn new(direction: Direction, distance: u64) -> Self {
+        Self {
+            direction,
+            distance,
+        }
+    }
+
+    /// The distance, negative to the left
+    pub fn signed_distance(&self) -> i128 {
+        match self.direction {
+            Direction::Left => -i128::from(self.distance),
+            Direction::Right => i128::from(self.distance),
+        }
+    }
+
+    /// The rotation that undoes this one
+    pub fn inverse(&self) -> Self {
+        Self::new(self.direction.reversed(), self.distance)
+    }
+
+    /// The single rotation ending where all of `rotations` end, to the right when they cancel
+    /// out. `None` if it's more than `u64::MAX` clicks
+    pub fn compose(rotations: impl IntoIterator<Item = Self>) -> Option<Self> {
+        let net = rotations.into_iter().try_fold(0i128, |net, rotation| {
+            net.checked_add(rotation.signed_distance())
+        })?;
+        let direction = if net < 0 {
+            Direction::Left
+        } else {
+            Direction::Right
+        };
+        let distance = u64::try_from(net.unsigned_abs()).ok()?;
+        Some(Self::new(direction, distance))
+    }
+}
+
+impl From<i64> for Rotation {
+    /// Negative distances are to the left
+    fn from(distance: i64) -> Self {
+        let direction = if distance < 0 {
+            Direction::Left
+        } else {
+            Direction::Right
+        };
+        Self::new(direction, distance.unsigned_abs())
+    }
+}
+
+impl FromStr for Rotation {
+    type Err = ParseRotationError;
+
+    /// Parses `L<distance>` or `R<distance>`, the distance fitting in an `i64` like every
+    /// rotation the puzzle input has
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let direction = match chars.next() {
+            Some('L') => Direction::Left,
+            Some('R') => Direction::Right,
+            Some(c) => return Err(ParseRotationError::InvalidDirection(c)),
+            None => return Err(ParseRotationError::Empty),
+        };
+        let distance = chars.as_str();
+        // `u64::from_str` takes a leading `+`, which isn't a rotation
+        if !distance.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(ParseRotationError::MissingDistance);
+        }
+        let distance = distance
+            .parse()
+            .map_err(ParseRotationError::InvalidDistance)?;
+        if distance > i64::MAX.unsigned_abs() {
+            return Err(ParseRotationError::DistanceTooLarge(distance));
+        }
+        Ok(Self::new(direction, distance))
+    }
+}
+
+impl Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        };
+        write!(f, "{direction}{}", self.distance)
+    }
+}
+
+/// Where a dial of `size` at `start` ends after `rotation`
+fn turn(size: u64, start: u64, rotation: Rotation) -> u64 {
+    // `rem_euclid` is always in 0..size, so it fits back into a `u64`
+    (i128::from(start) + rotation.signed_distance()).rem_euclid(i128::from(size)) as u64
+}
+
+/// The clicks it takes a dial of `size` at `start` turning in `direction` to first reach
+/// `target`, a full turn when starting on it
+fn clicks_to_reach(size: u64, start: u64, direction: Direction, target: u64) -> u64 {
+    // reaching `target` is reaching zero on a dial numbered from `target`
+    let position = (i128::from(start) - i128::from(target)).rem_euclid(i128::from(size)) as u64;
+    match (position, direction) {
+        (0, _) => size,
+        (position, Direction::Left) => position,
+        (position, Direction::Right) => size - position,
+    }
+}
+
+/// How many times a dial of `size` at `start` goes through `target` during `rotation`
+/// without stopping on it
+fn passes(size: u64, start: u64, rotation: Rotation, target: u64) -> u64 {
+    let to_target = clicks_to_reach(size, start, rotation.direction, target);
+    let num_reached = match rotation.distance.checked_sub(to_target) {
+        Some(after_first) => after_first / size + 1,
+        None => 0,
+    };
+    // stopping on `target` isn't passing it, and a rotation stopping on it reached it at least once
+    let stops_on_target = rotation.distance != 0 && turn(size, start, rotation) == target;
+    num_reached - u64::from(stops_on_target)
+}
+
+/// A dial numbered `0..size`, pointing at one of its numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    position: u64,
+    size: u64,
+}
+
+impl Dial {
+    pub const DEFAULT_SIZE: u64 = 100;
+    pub const DEFAULT_START: u64 = 50;
+
+    /// A dial of the default size, `None` if `start` isn't on it
+    pub const fn new(start: u64) -> Option<Self> {
+        Self::with_size(Self::DEFAULT_SIZE, start)
+    }
+
+    /// `None` if the dial has fewer than 2 numbers or `start` isn't on it
+    pub const fn with_size(size: u64, start: u64) -> Option<Self> {
+        if size < 2 || start >= size {
+            return None;
+        }
+        Some(Self {
+            position: start,
+            size,
+        })
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.is_at(0)
+    }
+
+    pub fn is_at(&self, position: u64) -> bool {
+        self.position == position
+    }
+
+    /// Rotates the dial, returning how many times it passed zero without stopping on it
+    pub fn rotate(&mut self, rotation: Rotation) -> u64 {
+        let num_passes = passes(self.size, self.position, rotation, 0);
+        self.position = turn(self.size, self.position, rotation);
+        num_passes
+    }
+
+    /// Rotates the dial, telling where the rotation went
+    pub fn step(&mut self, rotation: Rotation) -> Step {
+        let start = self.position;
+        *self += rotation;
+        Step {
+            start,
+            end: self.position,
+            rotation,
+            size: self.size,
+        }
+    }
+
+    /// Rotates the dial once for every rotation, telling where every rotation went
+    pub fn steps<I: IntoIterator<Item = Rotation>>(self, rotations: I) -> Steps<I::IntoIter> {
+        Steps {
+            dial: self,
+            rotations: rotations.into_iter(),
+        }
+    }
+
+    /// Rotates the dial by a distance that is negative to the left, returning how many times
+    /// it passed zero without stopping on it
+    pub fn add_assign_count_saturations(&mut self, rhs: i64) -> u64 {
+        self.rotate(rhs.into())
+    }
+
+    /// Undoes `rotations` from the last one back, leaving the dial where it was before them
+    pub fn rewind(&mut self, rotations: &[Rotation]) {
+        for rotation in rotations.iter().rev() {
+            *self += rotation.inverse();
+        }
+    }
+}
+
+/// One rotation of a dial, from where it started to where it ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub start: u64,
+    pub end: u64,
+    pub rotation: Rotation,
+    size: u64,
+}
+
+impl Step {
+    pub fn direction(&self) -> Direction {
+        self.rotation.direction
+    }
+
+    /// How many positions the dial that made the step has
+    pub fn dial_size(&self) -> u64 {
+        self.size
+    }
+
+    /// How many times the dial went through `position` without stopping on it,
+    /// 0 for positions that aren't on the dial
+    pub fn passes(&self, position: u64) -> u64 {
+        if position >= self.size {
+            return 0;
+        }
+        passes(self.size, self.start, self.rotation, position)
+    }
+
+    /// How many times the dial landed on or passed through `position`
+    pub fn visits(&self, position: u64) -> u64 {
+        self.passes(position) + u64::from(self.end == position)
+    }
+
+    /// How many whole turns of the dial the rotation makes
+    pub fn full_rotations(&self) -> u64 {
+        self.rotation.distance / self.size
+    }
+
+    /// The first click that reached `position`, counting from 1. `None` if no click did,
+    /// even when the dial didn't move from `position`
+    pub fn first_click_at(&self, position: u64) -> Option<u64> {
+        if position >= self.size {
+            return None;
+        }
+        let clicks = clicks_to_reach(self.size, self.start, self.rotation.direction, position);
+        (clicks <= self.rotation.distance).then_some(clicks)
+    }
+}
+
+/// How many times `dial` lands on or passes through every one of its positions while going
+/// through `rotations`, as if adding up [`Step::visits`] for every position. The counts are
+/// `u128`s as enough rotations of up to `i64::MAX` clicks add up to more than a `u64`.
+/// It needs a count for every position, so it fails for dials too large to fit in memory
+pub fn visit_counts(
+    dial: Dial,
+    rotations: impl IntoIterator<Item = Rotation>,
+) -> Result<Vec<u128>, TryReserveError> {
+    let size = usize::try_from(dial.size).unwrap_or(usize::MAX);
+    // the change in count from one position to the next, so every step is only 2 updates.
+    // Each step changes it by at most 1, so it can't get anywhere near the `i128` limits
+    let mut changes: Vec<i128> = Vec::new();
+    changes.try_reserve_exact(size)?;
+    changes.resize(size, 0);
+    // every position is visited once per full turn
+    let mut full_turns: u128 = 0;
+
+    for step in dial.steps(rotations) {
+        let Rotation {
+            direction,
+            distance,
+        } = step.rotation;
+        full_turns += u128::from(distance / dial.size);
+        // the last partial turn visits `rest` positions ending on `end` (to the right) or
+        // starting on it (to the left), or only lands on `end` when the dial doesn't move
+        let rest = distance % dial.size;
+        let (first, len) = match direction {
+            _ if distance == 0 => (step.end, 1),
+            Direction::Right => ((step.start + 1) % dial.size, rest),
+            Direction::Left => (step.end, rest),
+        };
+        if len == 0 {
+            continue;
+        }
+        // positions and lengths are below `size`, which fits in a `usize` once reserved
+        let (first, len) = (first as usize, len as usize);
+        changes[first] += 1;
+        match first + len {
+            end if end < size => changes[end] -= 1,
+            // the arc wraps around to the start of the dial
+            end if end > size => {
+                changes[0] += 1;
+                changes[end - size] -= 1;
+            }
+            _ => {}
+        }
+    }
+
+    // the partial turns over a position never add up to less than 0
+    let mut partial_turns = 0;
+    Ok(changes
+        .into_iter()
+        .map(|change| {
+            partial_turns += change;
+            full_turns + partial_turns.unsigned_abs()
+        })
+        .collect())
+}
+
+/// The steps of a dial going through rotations, see [`Dial::steps`]
+#[derive(Debug, Clone)]
+pub struct Steps<I> {
+    dial: Dial,
+    rotations: I,
+}
+
+impl<I> Steps<I> {
+    /// The dial after the steps so far
+    pub fn dial(&self) -> Dial {
+        self.dial
+    }
+}
+
+impl<I: Iterator<Item = Rotation>> Iterator for Steps<I> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        let rotation = self.rotations.next()?;
+        Some(self.dial.step(rotation))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rotations.size_hint()
+    }
+}
+
+impl core::ops::AddAssign<i64> for Dial {
+    fn add_assign(&mut self, rhs: i64) {
+        // ignore num rotations for part1
+        self.add_assign_count_saturations(rhs);
+    }
+}
+
+impl core::ops::AddAssign<Rotation> for Dial {
+    fn add_assign(&mut self, rotation: Rotation) {
+        self.rotate(rotation);
+    }
+}
+
+/// What a dial counts about zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZeroCount {
+    /// Rotations that stop on zero, like part1
+    Landings,
+    /// Clicks that land on zero, like part2
+    Visits,
+}
+
+/// Every position a dial of `size` can start at for `rotations` to reach zero exactly `count`
+/// times, `None` if a dial can't have that size.
+///
+/// Whether a rotation reaches zero only depends on the start being in one range of starts
+/// (besides its full turns, which reach zero from anywhere), so this sweeps over those
+/// ranges instead of trying every start
+pub fn starts_with_count(
+    size: u64,
+    rotations: &[Rotation],
+    counted: ZeroCount,
+    count: u64,
+) -> Option<IntervalSet> {
+    Dial::with_size(size, 0)?;
+    // how many more ranges contain the starts from every key on
+    let mut changes: BTreeMap<u64, i64> = BTreeMap::new();
+    let mut add = |start: u64, end: u64| {
+        *changes.entry(start).or_default() += 1;
+        if end + 1 < size {
+            *changes.entry(end + 1).or_default() -= 1;
+        }
+    };
+    let mut full_turns: u128 = 0;
+    // where the dial is relative to its start, before every rotation
+    let mut offset = 0;
+
+    for &rotation in rotations {
+        let before = offset;
+        offset = turn(size, offset, rotation);
+        // the positions the dial reaches zero from, after the rotation for landings and
+        // before it for visits
+        let (at, reaching) = match counted {
+            ZeroCount::Landings => (offset, Some((0, 0))),
+            ZeroCount::Visits => {
+                full_turns += u128::from(rotation.distance / size);
+                let reaching = match (rotation.direction, rotation.distance % size) {
+                    _ if rotation.distance == 0 => Some((0, 0)),
+                    (_, 0) => None,
+                    (Direction::Right, rest) => Some((size - rest, size - 1)),
+                    (Direction::Left, rest) => Some((1, rest)),
+                };
+                (before, reaching)
+            }
+        };
+        let Some((first, last)) = reaching else {
+            continue;
+        };
+
+        // the starts that put the dial on `first..=last`, wrapping around the end of the dial
+        let start = (i128::from(first) - i128::from(at)).rem_euclid(i128::from(size)) as u64;
+        let len = last - first;
+        match start.checked_add(len) {
+            Some(end) if end < size => add(start, end),
+            _ => {
+                add(start, size - 1);
+                add(0, len - (size - start));
+            }
+        }
+    }
+
+    let mut starts = IntervalSet::new();
+    let mut num_ranges: i64 = 0;
+    let mut from = 0;
+    let mut keep = |from: u64, to: u64, num_ranges: i64| {
+        if full_turns + num_ranges as u128 == u128::from(count) {
+            starts.extend(InclusiveRange::new(from, to));
+        }
+    };
+    for (at, change) in changes {
+        if at > from {
+            keep(from, at - 1, num_ranges);
+        }
+        num_ranges += change;
+        from = at;
+    }
+    keep(from, size - 1, num_ranges);
+    Some(starts)
+}
+
+/// What a plan makes the most of, besides reaching every target. A zero crossing is going
+/// through zero without stopping on it, like [`Dial::rotate`] counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Crossings {
+    /// Only the fewest clicks, to the right when both ways are as short
+    #[default]
+    Any,
+    /// The fewest zero crossings, then the fewest clicks
+    Fewest,
+    /// The most zero crossings, then the fewest clicks
+    Most,
+}
+
+/// The rotations that take `dial` to every one of `targets` in order, a rotation per target
+/// going whichever way round `crossings` prefers. Rotations are always less than a full turn,
+/// or `Most` could cross zero any number of times. Where the dial goes doesn't depend on the
+/// way it got there, so the best way to every target makes the best plan.
+/// `None` if a target isn't on the dial
+pub fn plan(mut dial: Dial, targets: &[u64], crossings: Crossings) -> Option<Vec<Rotation>> {
+    let mut rotations = Vec::with_capacity(targets.len());
+    for &target in targets {
+        if target >= dial.size {
+            return None;
+        }
+        let (position, size) = (i128::from(dial.position), i128::from(dial.size));
+        // both are in 0..size, so they fit back into a `u64`
+        let right = (i128::from(target) - position).rem_euclid(size) as u64;
+        let left = (position - i128::from(target)).rem_euclid(size) as u64;
+        let ways = [
+            Rotation::new(Direction::Right, right),
+            Rotation::new(Direction::Left, left),
+        ];
+        let passes = |rotation| passes(dial.size, dial.position, rotation, 0);
+        // the first of the best, so ties go to the right
+        let rotation = match crossings {
+            Crossings::Any => ways.into_iter().min_by_key(|way| way.distance),
+            Crossings::Fewest => ways
+                .into_iter()
+                .min_by_key(|way| (passes(*way), way.distance)),
+            Crossings::Most => ways
+                .into_iter()
+                .min_by_key(|way| (Reverse(passes(*way)), way.distance)),
+        }
+        .expect("there are two ways");
+        dial += rotation;
+        rotations.push(rotation);
+    }
+    Some(rotations)
+}
+
+/// Turning dial `from` of a lock turns dial `to` `ratio` times as many clicks,
+/// the other way when `ratio` is negative
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gear {
+    pub from: usize,
+    pub to: usize,
+    pub ratio: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockError {
+    UnknownDial(usize),
+    /// Turning a dial ends up turning it again through its gears
+    GearLoop,
+    /// A dial is turned more than `u64::MAX` clicks by a single turn
+    TooFar {
+        dial: usize,
+    },
+    /// A dial stops on or passes zero more than `u64::MAX` times in all
+    TooManyZeros {
+        dial: usize,
+    },
+}
+
+impl Display for LockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownDial(dial) => write!(f, "there is no dial {dial}"),
+            Self::GearLoop => write!(f, "the gears form a loop"),
+            Self::TooFar { dial } => write!(f, "dial {dial} is turned too far at once"),
+            Self::TooManyZeros { dial } => {
+                write!(f, "dial {dial} reaches zero too many times to count")
+            }
+        }
+    }
+}
+
+impl core::error::Error for LockError {}
+
+/// How often a dial of a lock stopped on and passed zero
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ZeroCounts {
+    /// Turns that left the dial at zero, like part1 counts
+    pub landings: u64,
+    /// Times the dial went through zero without stopping on it, added to the landings in part2
+    pub passes: u64,
+}
+
+/// Dials numbered from 0, some of them turning others through gears
+#[derive(Debug, Clone)]
+pub struct Lock {
+    dials: Vec<Dial>,
+    /// Ordered so the gears turning a dial come before the gears it turns
+    gears: Vec<Gear>,
+    counts: Vec<ZeroCounts>,
+}
+
+impl Lock {
+    pub fn new(dials: Vec<Dial>, mut gears: Vec<Gear>) -> Result<Self, LockError> {
+        if let Some(gear) = gears
+            .iter()
+            .find(|gear| gear.from >= dials.len() || gear.to >= dials.len())
+        {
+            return Err(LockError::UnknownDial(gear.from.max(gear.to)));
+        }
+
+        // Kahn's algorithm, the dials no gear turns first
+        let mut num_driving = vec![0usize; dials.len()];
+        for gear in &gears {
+            num_driving[gear.to] += 1;
+        }
+        let mut order: Vec<usize> = (0..dials.len()).filter(|&i| num_driving[i] == 0).collect();
+        let mut next = 0;
+        while let Some(&dial) = order.get(next) {
+            next += 1;
+            for gear in gears.iter().filter(|gear| gear.from == dial) {
+                num_driving[gear.to] -= 1;
+                if num_driving[gear.to] == 0 {
+                    order.push(gear.to);
+                }
+            }
+        }
+        if order.len() < dials.len() {
+            return Err(LockError::GearLoop);
+        }
+        let mut rank = vec![0; dials.len()];
+        for (i, dial) in order.into_iter().enumerate() {
+            rank[dial] = i;
+        }
+        gears.sort_by_key(|gear| rank[gear.from]);
+
+        let counts = vec![ZeroCounts::default(); dials.len()];
+        Ok(Self {
+            dials,
+            gears,
+            counts,
+        })
+    }
+
+    pub fn dials(&self) -> &[Dial] {
+        &self.dials
+    }
+
+    /// The counts of every dial so far
+    pub fn counts(&self) -> &[ZeroCounts] {
+        &self.counts
+    }
+
+    /// Turns `dial` and every dial geared to it. Nothing moves if that fails
+    pub fn turn(&mut self, dial: usize, rotation: Rotation) -> Result<(), LockError> {
+        if dial >= self.dials.len() {
+            return Err(LockError::UnknownDial(dial));
+        }
+        // the net clicks of every dial the turn reaches, negative to the left
+        let mut clicks: Vec<Option<i128>> = vec![None; self.dials.len()];
+        clicks[dial] = Some(rotation.signed_distance());
+        for gear in &self.gears {
+            let Some(driving) = clicks[gear.from] else {
+                continue;
+            };
+            let too_far = LockError::TooFar { dial: gear.to };
+            let driven = driving
+                .checked_mul(i128::from(gear.ratio))
+                .ok_or(too_far.clone())?;
+            let total = clicks[gear.to].unwrap_or(0).checked_add(driven);
+            clicks[gear.to] = Some(total.ok_or(too_far)?);
+        }
+
+        let mut rotations = Vec::new();
+        for (dial, clicks) in clicks.into_iter().enumerate() {
+            let Some(clicks) = clicks else {
+                continue;
+            };
+            let direction = if clicks < 0 {
+                Direction::Left
+            } else {
+                Direction::Right
+            };
+            let distance =
+                u64::try_from(clicks.unsigned_abs()).map_err(|_| LockError::TooFar { dial })?;
+            rotations.push((dial, Rotation::new(direction, distance)));
+        }
+
+        // every dial with its new counts, before changing any of them
+        let mut turned = Vec::with_capacity(rotations.len());
+        for (dial, rotation) in rotations {
+            let mut turned_dial = self.dials[dial];
+            let num_passes = turned_dial.rotate(rotation);
+            let ZeroCounts { landings, passes } = self.counts[dial];
+            let too_many = LockError::TooManyZeros { dial };
+            let counts = ZeroCounts {
+                landings: landings
+                    .checked_add(u64::from(turned_dial.is_zero()))
+                    .ok_or(too_many.clone())?,
+                passes: passes.checked_add(num_passes).ok_or(too_many)?,
+            };
+            turned.push((dial, turned_dial, counts));
+        }
+        for (dial, turned_dial, counts) in turned {
+            self.dials[dial] = turned_dial;
+            self.counts[dial] = counts;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    fn rotation(s: &str) -> Rotation {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_rotation() {
+        assert_eq!(rotation("L68"), Rotation::new(Direction::Left, 68));
+        assert_eq!(rotation("R0"), Rotation::new(Direction::Right, 0));
+        assert_eq!(
+            rotation("L9223372036854775807"),
+            Rotation::new(Direction::Left, i64::MAX as u64)
+        );
+        assert_eq!(rotation("L48").to_string(), "L48");
+
+        assert_eq!("".parse::<Rotation>(), Err(ParseRotationError::Empty));
+        assert_eq!(
+            "X5".parse::<Rotation>(),
+            Err(ParseRotationError::InvalidDirection('X'))
+        );
+        assert_eq!(
+            "L+5".parse::<Rotation>(),
+            Err(ParseRotationError::MissingDistance)
+        );
+        assert_eq!(
+            "R9223372036854775808".parse::<Rotation>(),
+            Err(ParseRotationError::DistanceTooLarge(1 << 63))
+        );
+        assert!(matches!(
+            "R5x".parse::<Rotation>(),
+            Err(ParseRotationError::InvalidDistance(_))
+        ));
+    }
+
+    #[test]
+    fn test_compose_and_inverse() {
+        let rotations = [rotation("L68"), rotation("R48"), rotation("L5")];
+        assert_eq!(Rotation::compose(rotations), Some(rotation("L25")));
+        assert_eq!(
+            Rotation::compose([rotation("L5"), rotation("R5")]),
+            Some(rotation("R0"))
+        );
+        assert_eq!(Rotation::compose([]), Some(rotation("R0")));
+        assert_eq!(
+            Rotation::compose([Rotation::new(Direction::Right, u64::MAX), rotation("R1")]),
+            None
+        );
+        assert_eq!(rotation("L68").inverse(), rotation("R68"));
+
+        // the composed rotation ends in the same place as the sequence
+        let mut step_by_step = Dial::new(50).unwrap();
+        for rotation in rotations {
+            step_by_step += rotation;
+        }
+        let mut at_once = Dial::new(50).unwrap();
+        at_once += Rotation::compose(rotations).unwrap();
+        assert_eq!(step_by_step, at_once);
+    }
+
+    #[test]
+    fn test_rewind() {
+        let rotations = [rotation("L68"), rotation("L30"), rotation("R48")];
+        let mut dial = Dial::new(50).unwrap();
+        for rotation in rotations {
+            dial += rotation;
+        }
+        assert_eq!(dial.position(), 0);
+        dial.rewind(&rotations);
+        assert_eq!(dial.position(), 50);
+    }
+
+    #[test]
+    fn test_steps() {
+        let rotations = ["L68", "L30", "R48"].map(rotation);
+        let mut steps = Dial::new(50).unwrap().steps(rotations);
+        let step = steps.next().unwrap();
+        assert_eq!((step.start, step.end), (50, 82));
+        assert_eq!(step.direction(), Direction::Left);
+        assert_eq!(step.passes(0), 1);
+        assert_eq!(step.passes(50), 0);
+        assert_eq!(step.passes(82), 0);
+        assert_eq!(step.passes(83), 1);
+        assert_eq!(step.passes(100), 0);
+        assert_eq!(step.first_click_at(0), Some(50));
+        assert_eq!(step.first_click_at(50), None);
+        assert_eq!(step.full_rotations(), 0);
+        assert_eq!(step.dial_size(), 100);
+
+        let ends: Vec<u64> = steps.by_ref().map(|step| step.end).collect();
+        assert_eq!(ends, [52, 0]);
+        assert_eq!(steps.dial().position(), 0);
+
+        // the clicks go through 4..=28, and stopping on 8 isn't passing it
+        let step = Dial::with_size(10, 3)
+            .unwrap()
+            .steps([rotation("R25")])
+            .next()
+            .unwrap();
+        let passes: Vec<u64> = (0..10).map(|position| step.passes(position)).collect();
+        assert_eq!(passes, [2, 2, 2, 2, 3, 3, 3, 3, 2, 2]);
+        assert_eq!(step.full_rotations(), 2);
+        assert_eq!(step.first_click_at(3), Some(10));
+        assert_eq!(step.first_click_at(8), Some(5));
+    }
+
+    #[test]
+    fn test_visits() {
+        let rotations = ["L68", "L30", "R48", "R0", "L205"].map(rotation);
+        for (size, start) in [(100, 50), (7, 0), (2, 1)] {
+            let dial = Dial::with_size(size, start).unwrap();
+            let counts = visit_counts(dial, rotations).unwrap();
+            for position in 0..size {
+                let visits: u128 = dial
+                    .steps(rotations)
+                    .map(|step| u128::from(step.visits(position)))
+                    .sum();
+                assert_eq!(
+                    counts[position as usize], visits,
+                    "{size} {start} {position}"
+                );
+            }
+        }
+
+        let step = Dial::new(50)
+            .unwrap()
+            .steps([rotation("R0")])
+            .next()
+            .unwrap();
+        assert_eq!((step.visits(50), step.visits(51)), (1, 0));
+        assert!(visit_counts(Dial::with_size(u64::MAX, 0).unwrap(), []).is_err());
+
+        // more than `u64::MAX` visits of each position
+        let counts = visit_counts(
+            Dial::with_size(2, 0).unwrap(),
+            [Rotation::from(i64::MAX); 5],
+        );
+        let full_turns = 5 * (u128::from(i64::MAX.unsigned_abs()) / 2);
+        assert_eq!(counts.unwrap(), [full_turns + 2, full_turns + 3]);
+    }
+
+    #[test]
+    fn test_starts_with_count() {
+        let rotations = [
+            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
+        ]
+        .map(rotation);
+        let starts = starts_with_count(100, &rotations, ZeroCount::Landings, 3).unwrap();
+        assert!(starts.contains(50));
+
+        for size in [2, 3, 7, 100] {
+            for counted in [ZeroCount::Landings, ZeroCount::Visits] {
+                let rotations = ["R0", "L68", "R3", "L100", "R205", "L7"].map(rotation);
+                let mut by_count: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+                for start in 0..size {
+                    let count = Dial::with_size(size, start)
+                        .unwrap()
+                        .steps(rotations)
+                        .map(|step| match counted {
+                            ZeroCount::Landings => u64::from(step.end == 0),
+                            ZeroCount::Visits => step.visits(0),
+                        })
+                        .sum();
+                    by_count.entry(count).or_default().push(start);
+                }
+                for count in 0..12 {
+                    let starts = starts_with_count(size, &rotations, counted, count).unwrap();
+                    let starts: Vec<u64> = starts.iter().flat_map(|range| range.iter()).collect();
+                    let expected = by_count.get(&count).cloned().unwrap_or_default();
+                    assert_eq!(starts, expected, "{size} {counted:?} {count}");
+                }
+            }
+        }
+
+        // too many starts to try one by one
+        let starts = starts_with_count(u64::MAX, &[rotation("R10")], ZeroCount::Visits, 1).unwrap();
+        assert_eq!(starts.len(), 10);
+        assert!(starts.contains(u64::MAX - 1) && !starts.contains(0));
+        assert!(starts_with_count(1, &[], ZeroCount::Landings, 0).is_none());
+    }
+
+    #[test]
+    fn test_plan() {
+        let dial = Dial::new(50).unwrap();
+        let rotations = plan(dial, &[82, 0, 0, 99, 49], Crossings::Any).unwrap();
+        let printed: Vec<String> = rotations.iter().map(Rotation::to_string).collect();
+        assert_eq!(printed, ["R32", "R18", "R0", "L1", "R50"]);
+
+        // from 50 the short way to 10 is left, the long way crosses zero
+        let dial = Dial::new(50).unwrap();
+        assert_eq!(
+            plan(dial, &[10], Crossings::Any),
+            Some(vec![rotation("L40")])
+        );
+        assert_eq!(
+            plan(dial, &[10], Crossings::Fewest),
+            Some(vec![rotation("L40")])
+        );
+        assert_eq!(
+            plan(dial, &[10], Crossings::Most),
+            Some(vec![rotation("R60")])
+        );
+        // from 10 the short way to 90 crosses zero, so fewest takes the long way
+        let dial = Dial::with_size(100, 10).unwrap();
+        let fewest = plan(dial, &[90, 90], Crossings::Fewest).unwrap();
+        assert_eq!(fewest, [rotation("R80"), rotation("R0")]);
+        let most = plan(dial, &[90, 90], Crossings::Most).unwrap();
+        assert_eq!(most, [rotation("L20"), rotation("R0")]);
+
+        // halfway round on both sides, only the left passes zero
+        let dial = Dial::with_size(10, 2).unwrap();
+        assert_eq!(plan(dial, &[7], Crossings::Any), Some(vec![rotation("R5")]));
+        assert_eq!(
+            plan(dial, &[7], Crossings::Fewest),
+            Some(vec![rotation("R5")])
+        );
+        assert_eq!(
+            plan(dial, &[7], Crossings::Most),
+            Some(vec![rotation("L5")])
+        );
+        assert_eq!(plan(dial, &[10], Crossings::Any), None);
+
+        let dial = Dial::with_size(u64::MAX, 1).unwrap();
+        let rotations = plan(dial, &[u64::MAX - 1, 3], Crossings::Any).unwrap();
+        assert_eq!(rotations, [rotation("L2"), rotation("R4")]);
+    }
+
+    #[test]
+    fn test_lock() {
+        let dials = vec![Dial::with_size(10, 0).unwrap(); 3];
+        let gears = vec![
+            Gear {
+                from: 1,
+                to: 2,
+                ratio: -1,
+            },
+            Gear {
+                from: 0,
+                to: 1,
+                ratio: 2,
+            },
+        ];
+        let mut lock = Lock::new(dials.clone(), gears.clone()).unwrap();
+        // dial 0 turns R5, so dial 1 turns R10 and dial 2 turns L10
+        lock.turn(0, rotation("R5")).unwrap();
+        let positions: Vec<u64> = lock.dials().iter().map(Dial::position).collect();
+        assert_eq!(positions, [5, 0, 0]);
+        lock.turn(1, rotation("L13")).unwrap();
+        let positions: Vec<u64> = lock.dials().iter().map(Dial::position).collect();
+        assert_eq!(positions, [5, 7, 3]);
+        let passes_and_landings: Vec<(u64, u64)> = lock
+            .counts()
+            .iter()
+            .map(|counts| (counts.passes, counts.landings))
+            .collect();
+        assert_eq!(passes_and_landings, [(0, 0), (1, 1), (1, 1)]);
+
+        assert_eq!(lock.turn(3, rotation("R1")), Err(LockError::UnknownDial(3)));
+        let mut far = Lock::new(
+            dials.clone(),
+            vec![Gear {
+                from: 0,
+                to: 1,
+                ratio: 2,
+            }],
+        )
+        .unwrap();
+        assert_eq!(
+            far.turn(0, Rotation::new(Direction::Right, u64::MAX)),
+            Err(LockError::TooFar { dial: 1 })
+        );
+        // nothing moved
+        assert_eq!(far.dials()[0].position(), 0);
+
+        // every turn passes zero `i64::MAX / 2` times, the fifth takes the passes past `u64::MAX`
+        let mut busy = Lock::new(vec![Dial::with_size(2, 0).unwrap()], Vec::new()).unwrap();
+        let rotation = Rotation::from(i64::MAX);
+        for _ in 0..4 {
+            busy.turn(0, rotation).unwrap();
+        }
+        let counts = busy.counts()[0];
+        assert_eq!(
+            busy.turn(0, rotation),
+            Err(LockError::TooManyZeros { dial: 0 })
+        );
+        assert_eq!((busy.dials()[0].position(), busy.counts()[0]), (0, counts));
+
+        let looped = [
+            gears[0],
+            Gear {
+                from: 2,
+                to: 0,
+                ratio: 1,
+            },
+            gears[1],
+        ];
+        assert_eq!(
+            Lock::new(dials.clone(), looped.to_vec()).unwrap_err(),
+            LockError::GearLoop
+        );
+        let unknown = Gear {
+            from: 0,
+            to: 5,
+            ratio: 1,
+        };
+        assert_eq!(
+            Lock::new(dials, vec![unknown]).unwrap_err(),
+            LockError::UnknownDial(5)
+        );
+    }
+
+    #[test]
+    fn test_dial() {
+        let mut dial = Dial::new(11).unwrap();
+        dial += 8;
+        assert_eq!(dial.position, 19);
+        dial += -19;
+        assert_eq!(dial.position, 0);
+
+        let mut dial = Dial::new(0).unwrap();
+        dial += -1;
+        assert_eq!(dial.position, 99);
+        dial += 1;
+        assert_eq!(dial.position, 0);
+
+        let mut dial = Dial::new(5).unwrap();
+        dial += -10;
+        assert_eq!(dial.position, 95);
+        dial += 5;
+        assert_eq!(dial.position, 0);
+    }
+
+    /// Turns the dial one click at a time, returning how often it passed zero and where it stopped
+    fn turn_click_by_click(size: u64, start: u64, rhs: i64) -> (u64, u64) {
+        let mut position = start;
+        let mut passes = 0;
+        for click in 0..rhs.unsigned_abs() {
+            position = if rhs < 0 {
+                (position + size - 1) % size
+            } else {
+                (position + 1) % size
+            };
+            // stopping on zero after the last click isn't passing it
+            if position == 0 && click + 1 != rhs.unsigned_abs() {
+                passes += 1;
+            }
+        }
+        (passes, position)
+    }
+
+    #[test]
+    fn test_large_rotations() {
+        let mut dial = Dial::new(50).unwrap();
+        assert_eq!(dial.add_assign_count_saturations(-i64::from(i16::MAX)), 328);
+        assert_eq!(dial.position, 83);
+        assert_eq!(dial.add_assign_count_saturations(i64::from(i16::MAX)), 328);
+        assert_eq!(dial.position, 50);
+
+        for size in [2, 3, 100, 997] {
+            for rhs in [40_000, -123_457, 1_000_003, -1_000_000] {
+                let start = size / 2;
+                let mut dial = Dial::with_size(size, start).unwrap();
+                let (passes, position) = turn_click_by_click(size, start, rhs);
+                assert_eq!(dial.add_assign_count_saturations(rhs), passes);
+                assert_eq!(dial.position(), position);
+            }
+        }
+    }
+
+    #[test]
+    fn test_extreme_rotations() {
+        // too many clicks to simulate, so count the multiples of `size` the clicks go through
+        for size in [2, 100, 7919, u64::MAX] {
+            for start in [0, 1, size - 1] {
+                for rhs in [i64::MAX, i64::MIN, i64::MIN + 1] {
+                    let (p, d, n) = (i128::from(start), i128::from(rhs), i128::from(size));
+                    let end = p + d;
+                    let zeros = if rhs > 0 {
+                        end.div_euclid(n) - p.div_euclid(n)
+                    } else {
+                        (p - 1).div_euclid(n) - (end - 1).div_euclid(n)
+                    };
+                    let stops_on_zero = end.rem_euclid(n) == 0;
+
+                    let mut dial = Dial::with_size(size, start).unwrap();
+                    let passes = dial.add_assign_count_saturations(rhs);
+                    assert_eq!(i128::from(passes), zeros - i128::from(stops_on_zero));
+                    assert_eq!(i128::from(dial.position()), end.rem_euclid(n));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sizes() {
+        assert!(Dial::with_size(1, 0).is_none());
+        assert!(Dial::with_size(2, 2).is_none());
+
+        for size in 2..8 {
+            for start in 0..size {
+                for rhs in -20..=20 {
+                    let mut dial = Dial::with_size(size, start).unwrap();
+                    let (passes, position) = turn_click_by_click(size, start, rhs);
+                    assert_eq!(dial.add_assign_count_saturations(rhs), passes);
+                    assert_eq!(dial.position(), position);
+                }
+            }
+        }
+    }
+}
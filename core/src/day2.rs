@@ -0,0 +1,72 @@
+/// An invalid ID is a number which is made only of some sequence of digits repeated twice
+pub fn is_valid_id(num: u64) -> bool {
+    let num_digits = num.checked_ilog10().unwrap_or(0) + 1;
+    if !num_digits.is_multiple_of(2) {
+        // odd number of digits, so we can't have a repeating sequence twice
+        return true;
+    }
+
+    let pow = 10u64.pow(num_digits / 2);
+    let higher_digits = num / pow;
+    let lower_digits = num % pow;
+    higher_digits != lower_digits
+}
+
+/// Now, an ID is invalid if it is made only of some sequence of digits repeated at least twice
+pub fn is_valid_id_part2(num: u64) -> bool {
+    // eprintln!("num: {num}");
+    if num < 10 {
+        // a single digit isn't repeated at least twice
+        return true;
+    }
+
+    let num_digits = num.ilog10() + 1;
+
+    for digits_to_check in 1..num_digits {
+        if !num_digits.is_multiple_of(digits_to_check) {
+            // if we can't split to repeated sequences it can't be an invalid ID
+            continue;
+        }
+        // eprintln!("digits_to_check: {digits_to_check}");
+
+        let modulo = 10u64.pow(digits_to_check);
+        let sequence = num % modulo;
+        // eprintln!("sequence: {sequence}");
+        let mut found_different = false;
+        let mut i = 1;
+        // a power that doesn't fit into a `u64` is definitely above `num`
+        while let Some(pow) = 10u64.checked_pow(digits_to_check * i) {
+            if pow > num {
+                break;
+            }
+            // eprintln!("pow: {pow}, checking: {}", (num / pow) % modulo);
+            if (num / pow) % modulo != sequence {
+                found_different = true;
+                break;
+            }
+            i += 1;
+        }
+
+        if !found_different {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_id_part2() {
+        assert!(!is_valid_id_part2(2121212121));
+        assert!(is_valid_id_part2(2121212120));
+        assert!(is_valid_id_part2(3));
+        assert!(is_valid_id_part2(0));
+        assert!(is_valid_id(0));
+        assert!(is_valid_id_part2(u64::MAX));
+        assert!(!is_valid_id_part2(11111111111111111111));
+    }
+}
@@ -0,0 +1,104 @@
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+/// A bank doesn't have enough batteries to turn on the requested amount
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotEnoughDigits {
+    pub needed: usize,
+    pub got: usize,
+}
+
+impl Display for NotEnoughDigits {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "need at least {} digits, got {}", self.needed, self.got)
+    }
+}
+
+impl core::error::Error for NotEnoughDigits {}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerBank {
+    digits: Vec<u8>,
+}
+
+impl PowerBank {
+    pub fn new(digits: Vec<u8>) -> Self {
+        Self { digits }
+    }
+
+    /// The index relative to `from`, and the value of the first highest digit in `from..to`
+    fn find_max_from_index(&self, from: usize, to: usize) -> Option<(usize, u8)> {
+        let digits = self.digits.get(from..to)?;
+        // we don't use `.max()` since it returns the last one if multiple are equal
+        let mut max_num_and_index = None;
+        for (i, d) in digits.iter().enumerate() {
+            if let Some((_, max_d)) = &max_num_and_index {
+                if d > max_d {
+                    max_num_and_index = Some((i, *d));
+                }
+            } else {
+                max_num_and_index = Some((i, *d));
+            }
+        }
+
+        max_num_and_index
+    }
+
+    fn not_enough_digits(&self, needed: usize) -> NotEnoughDigits {
+        NotEnoughDigits {
+            needed,
+            got: self.digits.len(),
+        }
+    }
+
+    pub fn sum_top_2(&self) -> Result<u16, NotEnoughDigits> {
+        if self.digits.len() < 2 {
+            return Err(self.not_enough_digits(2));
+        }
+        // we always want to first find the highest number that appears first,
+        // since no matter what it'll be higher than even if we find a 9
+        // that is after it
+        let (i, tens) = self
+            .find_max_from_index(0, self.digits.len() - 1)
+            .ok_or_else(|| self.not_enough_digits(2))?;
+        let (_, ones) = self
+            .find_max_from_index(i + 1, self.digits.len())
+            .ok_or_else(|| self.not_enough_digits(2))?;
+        Ok(u16::from(tens) * 10 + u16::from(ones))
+    }
+
+    pub fn sum_top_12(&self) -> Result<u64, NotEnoughDigits> {
+        if self.digits.len() < 12 {
+            return Err(self.not_enough_digits(12));
+        }
+        let mut sum = 0;
+        let mut from = 0;
+        for i in 0..12 {
+            let (next_from, value) = self
+                .find_max_from_index(from, self.digits.len() - 11 + i)
+                .ok_or_else(|| self.not_enough_digits(12))?;
+            from = from + next_from + 1;
+            sum += u64::from(value) * 10u64.pow((11 - i) as u32);
+        }
+
+        Ok(sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_sum_top() {
+        let bank = PowerBank::new(vec![8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 9]);
+        assert_eq!(bank.sum_top_2(), Ok(89));
+        assert_eq!(bank.sum_top_12(), Ok(811111111119));
+
+        let bank = PowerBank::new(vec![9]);
+        assert_eq!(bank.sum_top_2(), Err(NotEnoughDigits { needed: 2, got: 1 }));
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    grid,
+    stats::{self, Counter},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Cell {
+    Paper,
+    Nothing,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Grid {
+    cells: grid::Grid<Cell>,
+}
+
+impl Grid {
+    pub fn new(cells: grid::Grid<Cell>) -> Self {
+        Self { cells }
+    }
+
+    pub fn cells(&self) -> &grid::Grid<Cell> {
+        &self.cells
+    }
+
+    pub fn get_accessible_papers(&self) -> Vec<(usize, usize)> {
+        let mut accessible = vec![];
+        for (at, cell) in self.cells.iter() {
+            stats::add(Counter::CellsVisited, 1);
+            let Cell::Paper = cell else {
+                continue;
+            };
+
+            let num_adjecent_papers = self
+                .cells
+                .neighbours8(at)
+                .inspect(|_| stats::add(Counter::CellsVisited, 1))
+                .filter(|neighbour| self.cells[*neighbour] == Cell::Paper)
+                .take(4)
+                .count();
+            if num_adjecent_papers < 4 {
+                accessible.push(at);
+            }
+        }
+
+        accessible
+    }
+
+    pub fn remove_papers(&mut self, papers: &[(usize, usize)]) {
+        for at in papers {
+            if let Some(cell) = self.cells.get_mut(*at) {
+                *cell = Cell::Nothing;
+            }
+        }
+    }
+
+    /// Removes accessible papers until none are left, returning the papers removed in each round
+    pub fn peel(&mut self) -> Vec<Vec<(usize, usize)>> {
+        let mut rounds = Vec::new();
+        let mut accessible = self.get_accessible_papers();
+        while !accessible.is_empty() {
+            self.remove_papers(&accessible);
+            rounds.push(accessible);
+            accessible = self.get_accessible_papers();
+        }
+        rounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peel() {
+        use Cell::{Nothing as N, Paper as P};
+        // the center paper is surrounded until its corners are removed
+        let cells = grid::Grid::from_rows(vec![vec![P, P, P], vec![P, P, P], vec![P, N, P]]);
+        let mut grid = Grid::new(cells.unwrap());
+        assert_eq!(
+            grid.get_accessible_papers(),
+            vec![(0, 0), (0, 2), (2, 0), (2, 2)]
+        );
+        assert_eq!(
+            grid.peel(),
+            vec![
+                vec![(0, 0), (0, 2), (2, 0), (2, 2)],
+                vec![(0, 1), (1, 0), (1, 1), (1, 2)]
+            ]
+        );
+        assert!(grid.get_accessible_papers().is_empty());
+    }
+}
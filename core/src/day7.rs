@@ -0,0 +1,194 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet, btree_map::Entry},
+    vec,
+    vec::Vec,
+};
+use core::mem;
+
+use crate::{answer::Answer, grid::Grid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Location {
+    Empty,
+    Splitter,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Manifold {
+    grid: Grid<Location>,
+    start: (usize, usize),
+}
+
+impl Manifold {
+    pub fn new(grid: Grid<Location>, start: (usize, usize)) -> Self {
+        Self { grid, start }
+    }
+
+    /// `(row, column)` of the beam source
+    pub fn start(&self) -> (usize, usize) {
+        self.start
+    }
+
+    pub fn grid(&self) -> &Grid<Location> {
+        &self.grid
+    }
+
+    fn get(&self, row_column: (usize, usize)) -> Option<Location> {
+        self.grid.get(row_column).copied()
+    }
+}
+
+#[derive(Debug)]
+pub struct ManifoldWalker<'a> {
+    manifold: &'a Manifold,
+    beams: Vec<(usize, usize)>,
+}
+
+impl<'a> ManifoldWalker<'a> {
+    pub fn new(manifold: &'a Manifold) -> Self {
+        Self {
+            manifold,
+            beams: vec![manifold.start],
+        }
+    }
+
+    /// The current beam positions, sorted
+    pub fn beams(&self) -> &[(usize, usize)] {
+        &self.beams
+    }
+
+    pub fn step(&mut self) -> Option<usize> {
+        // every beam left the manifold through its sides
+        if self.beams.is_empty() {
+            return None;
+        }
+        let mut splits = 0;
+        let mut new_beams = BTreeSet::new();
+        let current_beams = mem::take(&mut self.beams);
+        for beam in current_beams {
+            let (next_row, next_column) = (beam.0 + 1, beam.1);
+
+            // we assume the final row can't have a splitter at the edge
+            if next_row == self.manifold.grid.height() {
+                return None;
+            }
+            let next = (next_row, next_column);
+
+            let Some(location) = self.manifold.get(next) else {
+                continue;
+            };
+
+            match location {
+                Location::Empty => {
+                    new_beams.insert(next);
+                }
+                Location::Splitter => {
+                    splits += 1;
+                    new_beams.insert((next_row, next_column + 1));
+                    // a splitter on the left edge sends its left beam out of the manifold
+                    if let Some(left_column) = next_column.checked_sub(1) {
+                        new_beams.insert((next_row, left_column));
+                    }
+                }
+            };
+        }
+        self.beams = new_beams.into_iter().collect();
+
+        Some(splits)
+    }
+}
+
+#[derive(Debug)]
+pub struct QuantumBeam {
+    pub at: (usize, usize),
+    pub num_timelines: Answer,
+}
+
+#[derive(Debug)]
+pub struct QuantumManifoldWalker<'a> {
+    manifold: &'a Manifold,
+    beams: Vec<QuantumBeam>,
+}
+
+impl<'a> QuantumManifoldWalker<'a> {
+    pub fn new(manifold: &'a Manifold) -> Self {
+        Self {
+            manifold,
+            beams: vec![QuantumBeam {
+                at: manifold.start,
+                num_timelines: Answer::ONE,
+            }],
+        }
+    }
+
+    /// The current beams, sorted by their position
+    pub fn beams(&self) -> &[QuantumBeam] {
+        &self.beams
+    }
+
+    /// The number of timelines split on this row, `None` once the beams left the manifold
+    pub fn step(&mut self) -> Option<Answer> {
+        // every beam left the manifold through its sides
+        if self.beams.is_empty() {
+            return None;
+        }
+        let mut splits = Answer::ZERO;
+        let mut new_beams = Vec::new();
+        let current_beams = mem::take(&mut self.beams);
+        for beam in current_beams {
+            let (next_row, next_column) = (beam.at.0 + 1, beam.at.1);
+
+            // we assume the final row can't have a splitter at the edge
+            if next_row == self.manifold.grid.height() {
+                return None;
+            }
+            let next = (next_row, next_column);
+
+            let Some(location) = self.manifold.get(next) else {
+                continue;
+            };
+
+            match location {
+                Location::Empty => {
+                    new_beams.push(QuantumBeam {
+                        at: next,
+                        num_timelines: beam.num_timelines,
+                    });
+                }
+                Location::Splitter => {
+                    splits += beam.num_timelines.clone();
+                    new_beams.push(QuantumBeam {
+                        at: (next_row, next_column + 1),
+                        num_timelines: beam.num_timelines.clone(),
+                    });
+                    // a splitter on the left edge sends its left beam out of the manifold
+                    if let Some(left_column) = next_column.checked_sub(1) {
+                        new_beams.push(QuantumBeam {
+                            at: (next_row, left_column),
+                            num_timelines: beam.num_timelines,
+                        });
+                    }
+                }
+            };
+        }
+        let mut new_beams_folded: BTreeMap<(usize, usize), QuantumBeam> = BTreeMap::new();
+        for new_beam in new_beams {
+            match new_beams_folded.entry(new_beam.at) {
+                Entry::Occupied(mut occupied) => {
+                    let folded = occupied.get_mut();
+                    folded.num_timelines += new_beam.num_timelines;
+                }
+                Entry::Vacant(vacant) => {
+                    vacant.insert(new_beam);
+                }
+            }
+        }
+
+        self.beams = new_beams_folded.into_values().collect();
+
+        Some(splits)
+    }
+}
@@ -0,0 +1,153 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+use core::cmp::Reverse;
+
+use crate::{
+    geometry::Point,
+    stats::{self, Counter},
+};
+
+pub type Location = Point<3>;
+
+/// Every pair of different locations as `(distance, first index, second index)`, closest first
+pub fn get_sorted_distances(locations: &[Location]) -> Vec<(u128, usize, usize)> {
+    let mut distances = Vec::with_capacity(locations.len() * locations.len());
+    for (i, location) in locations.iter().enumerate() {
+        for (j, other) in locations.iter().enumerate().skip(i) {
+            if location == other {
+                continue;
+            }
+
+            stats::add(Counter::PairDistances, 1);
+            // pairs too far apart to measure are the farthest apart
+            let distance = location.squared_euclidean(other).unwrap_or(u128::MAX);
+            distances.push((distance, i, j));
+        }
+    }
+
+    distances.sort_by_key(|(d, _, _)| *d);
+
+    distances
+}
+
+/// Groups of connected locations, holding their indices
+#[derive(Debug, Default)]
+pub struct Circuits(Vec<BTreeSet<usize>>);
+
+impl Circuits {
+    /// Every one of the `num_locations` locations in a circuit of its own
+    pub fn singletons(num_locations: usize) -> Self {
+        Self((0..num_locations).map(|i| BTreeSet::from([i])).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Puts both locations in the same circuit, merging their circuits if they're in different ones
+    pub fn connect(&mut self, first: usize, second: usize) {
+        let first_circuit = self.0.iter().position(|c| c.contains(&first));
+        let second_circuit = self.0.iter().position(|c| c.contains(&second));
+
+        match (first_circuit, second_circuit) {
+            (Some(first_circuit), Some(second_circuit)) => {
+                if first_circuit == second_circuit {
+                    return;
+                }
+                // remove the later one, so the index of the earlier one stays valid
+                let (keep, remove) = if first_circuit < second_circuit {
+                    (first_circuit, second_circuit)
+                } else {
+                    (second_circuit, first_circuit)
+                };
+                let removed = self.0.remove(remove);
+                self.0[keep].extend(removed);
+            }
+            (Some(first_circuit), None) => {
+                self.0[first_circuit].insert(second);
+            }
+            (None, Some(second_circuit)) => {
+                self.0[second_circuit].insert(first);
+            }
+            (None, None) => self.0.push(BTreeSet::from([first, second])),
+        }
+        stats::add(Counter::Merges, 1);
+    }
+
+    /// The circuits sorted from the largest
+    pub fn into_sorted(mut self) -> Vec<BTreeSet<usize>> {
+        self.0.sort_by_key(|x| Reverse(x.len()));
+        self.0
+    }
+}
+
+/// Connects the `num_connections` closest pairs of locations, returning the circuits
+/// sorted from the largest. Locations that weren't connected to anything aren't in any circuit
+pub fn connect_closest(locations: &[Location], num_connections: usize) -> Vec<BTreeSet<usize>> {
+    connect_closest_sorted(&get_sorted_distances(locations), num_connections)
+}
+
+/// [`connect_closest`] for pairs already sorted by [`get_sorted_distances`], so callers that
+/// need the pairs too only compute them once
+pub fn connect_closest_sorted(
+    sorted_distances: &[(u128, usize, usize)],
+    num_connections: usize,
+) -> Vec<BTreeSet<usize>> {
+    let mut circuits = Circuits::default();
+    for &(_, i, j) in sorted_distances.iter().take(num_connections) {
+        circuits.connect(i, j);
+    }
+
+    circuits.into_sorted()
+}
+
+/// Connects the closest pairs of locations until they're all in a single circuit, returning
+/// the last pair connected. `None` if nothing was connected or they can't all be connected
+pub fn connect_all(locations: &[Location]) -> Option<(usize, usize)> {
+    let mut circuits = Circuits::singletons(locations.len());
+    let mut distance_it = get_sorted_distances(locations).into_iter();
+
+    let mut last = None;
+    while circuits.len() != 1 {
+        let (_, i, j) = distance_it.next()?;
+        circuits.connect(i, j);
+        last = Some((i, j));
+    }
+
+    last
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    fn location(x: i64, y: i64, z: i64) -> Location {
+        Location::new([x, y, z])
+    }
+
+    #[test]
+    fn test_connect() {
+        let locations = [
+            location(0, 0, 0),
+            location(1, 1, 1),
+            location(10, 10, 10),
+            location(11, 11, 11),
+            location(100, 100, 100),
+        ];
+        assert_eq!(
+            connect_closest(&locations, 2),
+            vec![BTreeSet::from([0, 1]), BTreeSet::from([2, 3])]
+        );
+        assert_eq!(
+            connect_closest_sorted(&get_sorted_distances(&locations), 2),
+            connect_closest(&locations, 2)
+        );
+        assert_eq!(connect_all(&locations), Some((3, 4)));
+        assert_eq!(connect_all(&locations[..1]), None);
+    }
+}
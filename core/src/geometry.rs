@@ -0,0 +1,318 @@
+use alloc::vec::Vec;
+use core::ops::Index;
+
+/// A point in `N` dimensions with integer coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<const N: usize> {
+    pub coords: [i64; N],
+}
+
+impl<const N: usize> Point<N> {
+    pub const fn new(coords: [i64; N]) -> Self {
+        Self { coords }
+    }
+
+    fn axis_distances(&self, other: &Self) -> impl Iterator<Item = u64> {
+        self.coords
+            .iter()
+            .zip(other.coords)
+            .map(|(a, b)| a.abs_diff(b))
+    }
+
+    /// The squared euclidean distance, which orders points like the euclidean one without
+    /// needing a square root. Every squared axis fits into a `u128` but their sum might not
+    pub fn squared_euclidean(&self, other: &Self) -> Option<u128> {
+        self.axis_distances(other)
+            .try_fold(0u128, |sum, d| sum.checked_add(u128::from(d).pow(2)))
+    }
+
+    pub fn manhattan(&self, other: &Self) -> Option<u128> {
+        self.axis_distances(other)
+            .try_fold(0u128, |sum, d| sum.checked_add(u128::from(d)))
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> u64 {
+        self.axis_distances(other).max().unwrap_or(0)
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &Self::Output {
+        &self.coords[axis]
+    }
+}
+
+/// A list of `N` coordinates
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for Point<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.coords)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for Point<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // serde only implements arrays up to 32 items, and not for a const generic length
+        let coords = Vec::<i64>::deserialize(deserializer)?;
+        let len = coords.len();
+        let coords = coords.try_into().map_err(|_| {
+            serde::de::Error::invalid_length(len, &"as many coordinates as dimensions")
+        })?;
+        Ok(Self::new(coords))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    SquaredEuclidean,
+    Manhattan,
+    Chebyshev,
+}
+
+impl Metric {
+    /// `None` if the distance doesn't fit into a `u128`
+    pub fn distance<const N: usize>(self, a: &Point<N>, b: &Point<N>) -> Option<u128> {
+        match self {
+            Self::SquaredEuclidean => a.squared_euclidean(b),
+            Self::Manhattan => a.manhattan(b),
+            Self::Chebyshev => Some(u128::from(a.chebyshev(b))),
+        }
+    }
+
+    /// The smallest distance two points `axis_distance` apart on a single axis can have
+    fn lower_bound(self, axis_distance: u64) -> u128 {
+        match self {
+            Self::SquaredEuclidean => u128::from(axis_distance).pow(2),
+            Self::Manhattan | Self::Chebyshev => u128::from(axis_distance),
+        }
+    }
+}
+
+/// The smallest box holding a set of points, both corners included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox<const N: usize> {
+    pub min: Point<N>,
+    pub max: Point<N>,
+}
+
+impl<const N: usize> BoundingBox<N> {
+    /// `None` if there are no points
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Point<N>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+        let mut bounding_box = Self {
+            min: first,
+            max: first,
+        };
+        for point in points {
+            bounding_box.include(point);
+        }
+        Some(bounding_box)
+    }
+
+    /// Grows the box until it holds `point`
+    pub fn include(&mut self, point: &Point<N>) {
+        for axis in 0..N {
+            self.min.coords[axis] = self.min.coords[axis].min(point.coords[axis]);
+            self.max.coords[axis] = self.max.coords[axis].max(point.coords[axis]);
+        }
+    }
+
+    pub fn contains(&self, point: &Point<N>) -> bool {
+        (0..N).all(|axis| (self.min[axis]..=self.max[axis]).contains(&point[axis]))
+    }
+
+    /// The length of every side, counting both ends
+    pub fn size(&self) -> [u128; N] {
+        core::array::from_fn(|axis| u128::from(self.max[axis].abs_diff(self.min[axis])) + 1)
+    }
+}
+
+/// A k-d tree over a slice of points for nearest neighbour queries
+#[derive(Debug)]
+pub struct NearestNeighbours<'a, const N: usize> {
+    points: &'a [Point<N>],
+    /// Indices into `points`. Every subslice has its median at the middle, split on
+    /// the axis of its depth, with the smaller coordinates before it
+    tree: Vec<usize>,
+}
+
+impl<'a, const N: usize> NearestNeighbours<'a, N> {
+    pub fn new(points: &'a [Point<N>]) -> Self {
+        let mut tree: Vec<usize> = (0..points.len()).collect();
+        Self::build(points, &mut tree, 0);
+        Self { points, tree }
+    }
+
+    fn build(points: &[Point<N>], tree: &mut [usize], depth: usize) {
+        if tree.len() <= 1 || N == 0 {
+            return;
+        }
+        let axis = depth % N;
+        let middle = tree.len() / 2;
+        tree.select_nth_unstable_by_key(middle, |i| points[*i][axis]);
+        let (before, after) = tree.split_at_mut(middle);
+        Self::build(points, before, depth + 1);
+        Self::build(points, &mut after[1..], depth + 1);
+    }
+
+    /// The index of the point closest to `query` and its distance, ties going to any of them.
+    /// Distances that overflow count as the farthest possible
+    pub fn nearest(&self, query: &Point<N>, metric: Metric) -> Option<(usize, u128)> {
+        self.nearest_matching(query, metric, |_| true)
+    }
+
+    /// Like `nearest`, only considering the points whose index passes `filter`,
+    /// e.g. to skip the query point itself
+    pub fn nearest_matching(
+        &self,
+        query: &Point<N>,
+        metric: Metric,
+        filter: impl Fn(usize) -> bool,
+    ) -> Option<(usize, u128)> {
+        let mut best = None;
+        self.search(&self.tree, 0, query, metric, &filter, &mut best);
+        best
+    }
+
+    fn search(
+        &self,
+        tree: &[usize],
+        depth: usize,
+        query: &Point<N>,
+        metric: Metric,
+        filter: &impl Fn(usize) -> bool,
+        best: &mut Option<(usize, u128)>,
+    ) {
+        if tree.is_empty() {
+            return;
+        }
+        let middle = tree.len() / 2;
+        let index = tree[middle];
+        let point = &self.points[index];
+
+        if filter(index) {
+            let distance = metric.distance(query, point).unwrap_or(u128::MAX);
+            if best.is_none_or(|(_, best_distance)| distance < best_distance) {
+                *best = Some((index, distance));
+            }
+        }
+        if N == 0 {
+            // every point is the same, there's no axis to split on
+            self.search(&tree[..middle], depth, query, metric, filter, best);
+            self.search(&tree[middle + 1..], depth, query, metric, filter, best);
+            return;
+        }
+
+        let axis = depth % N;
+        let (before, after) = (&tree[..middle], &tree[middle + 1..]);
+        let (near, far) = if query[axis] < point[axis] {
+            (before, after)
+        } else {
+            (after, before)
+        };
+        self.search(near, depth + 1, query, metric, filter, best);
+        // the far side is at least as far as the splitting plane
+        let plane_distance = metric.lower_bound(query[axis].abs_diff(point[axis]));
+        if best.is_none_or(|(_, best_distance)| plane_distance < best_distance) {
+            self.search(far, depth + 1, query, metric, filter, best);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        let a = Point::new([1, -2, 3]);
+        let b = Point::new([4, 2, 3]);
+        assert_eq!(a.squared_euclidean(&b), Some(25));
+        assert_eq!(a.manhattan(&b), Some(7));
+        assert_eq!(a.chebyshev(&b), 4);
+
+        let min = Point::new([i64::MIN; 3]);
+        let max = Point::new([i64::MAX; 3]);
+        assert_eq!(min.squared_euclidean(&max), None);
+        assert_eq!(min.manhattan(&max), Some(3 * u128::from(u64::MAX)));
+        assert_eq!(min.chebyshev(&max), u64::MAX);
+        assert_eq!(
+            Point::new([i64::MIN]).squared_euclidean(&Point::new([i64::MAX])),
+            Some(u128::from(u64::MAX).pow(2))
+        );
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = [Point::new([1, 5]), Point::new([-3, 2]), Point::new([0, 9])];
+        let bounding_box = BoundingBox::from_points(&points).unwrap();
+        assert_eq!(bounding_box.min, Point::new([-3, 2]));
+        assert_eq!(bounding_box.max, Point::new([1, 9]));
+        assert_eq!(bounding_box.size(), [5, 8]);
+        assert!(bounding_box.contains(&Point::new([0, 2])));
+        assert!(!bounding_box.contains(&Point::new([0, 10])));
+        assert_eq!(BoundingBox::<2>::from_points(&[]), None);
+    }
+
+    #[test]
+    fn test_nearest_neighbours() {
+        // a pseudo random cloud, checked against comparing every point
+        let mut seed: i64 = 7;
+        let mut next = || {
+            seed = (seed * 1_103_515_245 + 12_345) % (1 << 31);
+            seed % 1000 - 500
+        };
+        let points: Vec<_> = (0..200)
+            .map(|_| Point::new([next(), next(), next()]))
+            .collect();
+        let index = NearestNeighbours::new(&points);
+
+        for metric in [
+            Metric::SquaredEuclidean,
+            Metric::Manhattan,
+            Metric::Chebyshev,
+        ] {
+            for (i, query) in points.iter().enumerate().take(50) {
+                let (found, distance) = index.nearest_matching(query, metric, |j| j != i).unwrap();
+                let brute_force = points
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, other)| metric.distance(query, other).unwrap())
+                    .min()
+                    .unwrap();
+                assert_eq!(distance, brute_force);
+                assert_eq!(metric.distance(query, &points[found]), Some(distance));
+            }
+        }
+
+        let single = [Point::new([0, 0])];
+        let index = NearestNeighbours::new(&single);
+        assert_eq!(
+            index.nearest(&Point::new([3, 4]), Metric::SquaredEuclidean),
+            Some((0, 25))
+        );
+        assert_eq!(
+            index.nearest_matching(&single[0], Metric::Manhattan, |_| false),
+            None
+        );
+        assert_eq!(
+            NearestNeighbours::<2>::new(&[]).nearest(&single[0], Metric::Manhattan),
+            None
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let point = Point::new([1, -2, 3]);
+        let json = serde_json::to_string(&point).unwrap();
+        assert_eq!(json, "[1,-2,3]");
+        assert_eq!(serde_json::from_str::<Point<3>>(&json).unwrap(), point);
+        assert!(serde_json::from_str::<Point<3>>("[1,2]").is_err());
+    }
+}
@@ -0,0 +1,415 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+/// `(row, column)` offsets of the 4 orthogonal neighbours
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// `(row, column)` offsets of all 8 neighbours, diagonals included
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseGridError {
+    InvalidChar {
+        c: char,
+        at: (usize, usize),
+    },
+    /// Every row must be as long as the first one
+    RaggedRow {
+        row: usize,
+        len: usize,
+        expected: usize,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidChar { c, at } => {
+                write!(f, "invalid character {c:?} at row {} column {}", at.0, at.1)
+            }
+            Self::RaggedRow { row, len, expected } => {
+                write!(f, "row {row} has {len} cells, expected {expected}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseGridError {}
+
+/// A rectangular grid stored row after row in a single `Vec`, indexed by `(row, column)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid without any cells is 0 by 0, so there aren't rows of nothing that `rows` skips
+    fn new(cells: Vec<T>, width: usize, height: usize) -> Self {
+        debug_assert_eq!(cells.len(), width * height);
+        let (width, height) = if cells.is_empty() {
+            (0, 0)
+        } else {
+            (width, height)
+        };
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses every line of `s` into a row, mapping each char with its position through `f`.
+    /// Chars `f` returns `None` for are an error.
+    pub fn parse(
+        s: &str,
+        mut f: impl FnMut((usize, usize), char) -> Option<T>,
+    ) -> Result<Self, ParseGridError> {
+        let mut cells = Vec::with_capacity(s.len());
+        let mut width = 0;
+        let mut height = 0;
+        for (row, line) in s.lines().enumerate() {
+            let mut len = 0;
+            for (column, c) in line.chars().enumerate() {
+                let cell = f((row, column), c).ok_or(ParseGridError::InvalidChar {
+                    c,
+                    at: (row, column),
+                })?;
+                cells.push(cell);
+                len += 1;
+            }
+
+            if row == 0 {
+                width = len;
+            } else if len != width {
+                return Err(ParseGridError::RaggedRow {
+                    row,
+                    len,
+                    expected: width,
+                });
+            }
+            height += 1;
+        }
+
+        Ok(Self::new(cells, width, height))
+    }
+
+    /// Returns `None` if the rows aren't all the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self::new(
+            rows.into_iter().flatten().collect(),
+            width,
+            height,
+        ))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, column): (usize, usize)) -> bool {
+        row < self.height && column < self.width
+    }
+
+    fn flat_index(&self, at: (usize, usize)) -> Option<usize> {
+        self.contains(at).then(|| at.0 * self.width + at.1)
+    }
+
+    pub fn get(&self, at: (usize, usize)) -> Option<&T> {
+        self.flat_index(at).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, at: (usize, usize)) -> Option<&mut T> {
+        self.flat_index(at).map(|i| &mut self.cells[i])
+    }
+
+    /// All positions, row after row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    /// All cells with their positions, row after row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    fn neighbours(
+        &self,
+        at: (usize, usize),
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let (width, height) = (self.width, self.height);
+        offsets
+            .iter()
+            .filter_map(move |(row_offset, column_offset)| {
+                let row = at.0.checked_add_signed(*row_offset)?;
+                let column = at.1.checked_add_signed(*column_offset)?;
+                (row < height && column < width).then_some((row, column))
+            })
+    }
+
+    /// The positions of the up to 4 orthogonal neighbours inside the grid
+    pub fn neighbours4(&self, at: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        self.neighbours(at, &NEIGHBOURS_4)
+    }
+
+    /// The positions of the up to 8 neighbours inside the grid, diagonals included
+    pub fn neighbours8(&self, at: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        self.neighbours(at, &NEIGHBOURS_8)
+    }
+
+    /// Panics if `row` is out of bounds
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "row {row} is out of bounds");
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` since `chunks` can't take 0, there are no cells to chunk then anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Panics if `column` is out of bounds
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(column < self.width, "column {column} is out of bounds");
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    /// The cells from `at` going down and to the right
+    pub fn diagonal(&self, at: (usize, usize)) -> impl Iterator<Item = &T> {
+        (0..)
+            .map(move |i| (at.0 + i, at.1 + i))
+            .map_while(|at| self.get(at))
+    }
+
+    /// The cells from `at` going down and to the left
+    pub fn anti_diagonal(&self, at: (usize, usize)) -> impl Iterator<Item = &T> {
+        (0..)
+            .map_while(move |i| Some((at.0 + i, at.1.checked_sub(i)?)))
+            .map_while(|at| self.get(at))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Builds a `width` by `height` grid where `f` gives the cell at every position
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut((usize, usize)) -> T) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for column in 0..width {
+                cells.push(f((row, column)));
+            }
+        }
+        Self::new(cells, width, height)
+    }
+
+    /// Renders every cell as the char `f` gives it, one line per row
+    pub fn display<F: Fn(&T) -> char>(&self, f: F) -> GridDisplay<'_, T, F> {
+        GridDisplay { grid: self, f }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::new(alloc::vec![value; width * height], width, height)
+    }
+
+    /// Mirrors the grid along its main diagonal, so rows become columns
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(row, column)| {
+            self[(column, row)].clone()
+        })
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(row, column)| {
+            self[(self.height - 1 - column, row)].clone()
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(row, column)| {
+            self[(column, self.width - 1 - row)].clone()
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, at: (usize, usize)) -> &Self::Output {
+        self.get(at)
+            .unwrap_or_else(|| panic!("{at:?} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, at: (usize, usize)) -> &mut Self::Output {
+        self.get_mut(at)
+            .unwrap_or_else(|| panic!("{at:?} is out of bounds"))
+    }
+}
+
+pub struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    f: F,
+}
+
+/// A list of rows
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Grid<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.rows())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Grid<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rows = Vec::<Vec<T>>::deserialize(deserializer)?;
+        Self::from_rows(rows).ok_or_else(|| serde::de::Error::custom("rows have different lengths"))
+    }
+}
+
+impl<T, F: Fn(&T) -> char> Display for GridDisplay<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.grid.rows().filter(|row| !row.is_empty()) {
+            for cell in row {
+                write!(f, "{}", (self.f)(cell))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use super::*;
+
+    fn digits(s: &str) -> Grid<u32> {
+        Grid::parse(s, |_, c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits("123\n456\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+
+        assert_eq!(
+            Grid::parse("12\n3x", |_, c| c.to_digit(10)),
+            Err(ParseGridError::InvalidChar { c: 'x', at: (1, 1) })
+        );
+        assert_eq!(
+            Grid::parse("12\n3", |_, c| c.to_digit(10)),
+            Err(ParseGridError::RaggedRow {
+                row: 1,
+                len: 1,
+                expected: 2
+            })
+        );
+        assert_eq!(digits("").height(), 0);
+
+        // blank lines are no rows at all, not rows without cells
+        let blank = digits("\n\n");
+        assert_eq!((blank.width(), blank.height()), (0, 0));
+        assert_eq!(blank.rows().count(), 0);
+        let rows = Grid::<u32>::from_rows(vec![vec![], vec![]]).unwrap();
+        assert_eq!(rows, blank);
+        assert_eq!(Grid::from_fn(3, 0, |_| 0u32), blank);
+        assert_eq!(Grid::filled(0, 2, 0u32).height(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 0 is out of bounds")]
+    fn test_blank_row() {
+        digits("\n\n").row(0);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits("123\n456\n789");
+        let neighbours: Vec<_> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(neighbours, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        let neighbours: Vec<_> = grid.neighbours8((2, 2)).map(|at| grid[at]).collect();
+        assert_eq!(neighbours, vec![5, 6, 8]);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = digits("123\n456\n789");
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5, 8]);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(
+            grid.diagonal((0, 1)).copied().collect::<Vec<_>>(),
+            vec![2, 6]
+        );
+        assert_eq!(
+            grid.anti_diagonal((0, 2)).copied().collect::<Vec<_>>(),
+            vec![3, 5, 7]
+        );
+    }
+
+    #[test]
+    fn test_rotate() {
+        let grid = digits("123\n456");
+        let show = |grid: &Grid<u32>| {
+            grid.display(|x| char::from_digit(*x, 10).unwrap())
+                .to_string()
+        };
+        assert_eq!(show(&grid.transpose()), "14\n25\n36\n");
+        assert_eq!(show(&grid.rotate_clockwise()), "41\n52\n63\n");
+        assert_eq!(show(&grid.rotate_counterclockwise()), "36\n25\n14\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(json, "[[1,2,3],[4,5,6]]");
+        assert_eq!(serde_json::from_str::<Grid<i32>>(&json).unwrap(), grid);
+        assert!(serde_json::from_str::<Grid<i32>>("[[1,2],[3]]").is_err());
+    }
+}
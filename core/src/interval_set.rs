@@ -0,0 +1,285 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{
+    range::{InclusiveRange, Integer},
+    stats::{self, Counter},
+};
+
+/// A set of integers stored as disjoint ranges, `u64` IDs unless said otherwise.
+///
+/// Ranges that overlap or touch are merged as they are inserted, so every gap between two
+/// stored ranges holds at least one ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        into = "Vec<InclusiveRange<T>>",
+        from = "Vec<InclusiveRange<T>>",
+        bound(
+            serialize = "T: Integer + serde::Serialize",
+            deserialize = "T: Integer + core::fmt::Display + serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct IntervalSet<T = u64> {
+    /// start -> end of every range
+    ranges: BTreeMap<T, T>,
+}
+
+// derived, it would only be there for `T: Default`
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, range: InclusiveRange<T>) {
+        let (mut start, mut end) = (range.start(), range.end());
+
+        if let Some((&before_start, &before_end)) = self.ranges.range(..=start).next_back()
+            && before_end.checked_next().is_none_or(|next| next >= start)
+        {
+            stats::add(Counter::MergeIterations, 1);
+            self.ranges.remove(&before_start);
+            start = before_start;
+            end = end.max(before_end);
+        }
+        while let Some((&after_start, &after_end)) = self.ranges.range(start..).next()
+            && end.checked_next().is_none_or(|next| after_start <= next)
+        {
+            stats::add(Counter::MergeIterations, 1);
+            self.ranges.remove(&after_start);
+            end = end.max(after_end);
+        }
+
+        self.ranges.insert(start, end);
+    }
+
+    pub fn remove(&mut self, range: InclusiveRange<T>) {
+        let before = self
+            .ranges
+            .range(..range.start())
+            .next_back()
+            .filter(|(_, end)| **end >= range.start());
+        let overlapping: Vec<_> = before
+            .into_iter()
+            .chain(self.ranges.range(range.start()..=range.end()))
+            .map(|(start, end)| (*start, *end))
+            .collect();
+
+        for (start, end) in overlapping {
+            self.ranges.remove(&start);
+            let stored = InclusiveRange::new(start, end).expect("stored ranges are valid");
+            let (left, _) = stored.split_at(range.start());
+            let right = range
+                .end()
+                .checked_next()
+                .and_then(|after| stored.split_at(after).1);
+            for piece in left.into_iter().chain(right) {
+                self.ranges.insert(piece.start(), piece.end());
+            }
+        }
+    }
+
+    /// O(log n) in the number of stored ranges
+    pub fn contains(&self, id: T) -> bool {
+        self.ranges
+            .range(..=id)
+            .next_back()
+            .is_some_and(|(_, end)| id <= *end)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of IDs in the set, a `u128` since the full `u64` range holds 2^64 of them
+    pub fn len(&self) -> u128 {
+        self.iter().map(|range| range.len()).sum()
+    }
+
+    /// The merged ranges, sorted
+    pub fn iter(&self) -> impl Iterator<Item = InclusiveRange<T>> + '_ {
+        self.ranges
+            .iter()
+            .map(|(start, end)| InclusiveRange::new(*start, *end).expect("stored ranges are valid"))
+    }
+
+    /// The IDs missing between the first and the last range, sorted
+    pub fn gaps(&self) -> impl Iterator<Item = InclusiveRange<T>> + '_ {
+        self.iter().zip(self.iter().skip(1)).map(|(before, after)| {
+            // merging touching ranges leaves at least one ID between these two
+            let gap = before
+                .end()
+                .checked_next()
+                .zip(after.start().checked_prev());
+            gap.and_then(|(start, end)| InclusiveRange::new(start, end))
+                .expect("ranges are merged")
+        })
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = Self::new();
+        let mut ours = self.iter().peekable();
+        let mut theirs = other.iter().peekable();
+        while let (Some(a), Some(b)) = (ours.peek(), theirs.peek()) {
+            if let Some(both) = a.intersection(b) {
+                intersection.insert(both);
+            }
+            // whichever ends first can't intersect anything else
+            if a.end() < b.end() {
+                ours.next();
+            } else {
+                theirs.next();
+            }
+        }
+        intersection
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+}
+
+impl<T: Integer> Extend<InclusiveRange<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = InclusiveRange<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Integer> FromIterator<InclusiveRange<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = InclusiveRange<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Integer> From<Vec<InclusiveRange<T>>> for IntervalSet<T> {
+    fn from(ranges: Vec<InclusiveRange<T>>) -> Self {
+        ranges.into_iter().collect()
+    }
+}
+
+/// The merged ranges, sorted by their start
+impl<T: Integer> From<IntervalSet<T>> for Vec<InclusiveRange<T>> {
+    fn from(set: IntervalSet<T>) -> Self {
+        set.iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+
+    fn set(ranges: &[(u64, u64)]) -> IntervalSet {
+        ranges
+            .iter()
+            .map(|(start, end)| InclusiveRange::new(*start, *end).unwrap())
+            .collect()
+    }
+
+    fn ranges(set: &IntervalSet) -> Vec<(u64, u64)> {
+        set.iter().map(|x| (x.start(), x.end())).collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let fresh = set(&[(3, 5), (10, 14), (16, 20), (12, 18)]);
+        assert_eq!(ranges(&fresh), vec![(3, 5), (10, 20)]);
+        assert_eq!(fresh.len(), 14);
+
+        // touching ranges merge, and one range can swallow several
+        let merged = set(&[(1, 2), (3, 4), (10, 11), (13, 14), (8, 20)]);
+        assert_eq!(ranges(&merged), vec![(1, 4), (8, 20)]);
+
+        let full = set(&[(u64::MAX, u64::MAX), (0, u64::MAX - 1)]);
+        assert_eq!(ranges(&full), vec![(0, u64::MAX)]);
+        assert_eq!(full.len(), 1 << 64);
+    }
+
+    #[test]
+    fn test_contains() {
+        let fresh = set(&[(3, 5), (10, 20)]);
+        let contained: Vec<_> = (0..22).filter(|id| fresh.contains(*id)).collect();
+        assert_eq!(contained, (3..=5).chain(10..=20).collect::<Vec<_>>());
+        assert!(!IntervalSet::new().contains(0));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut fresh = set(&[(3, 5), (10, 20)]);
+        fresh.remove(InclusiveRange::new(4, 12).unwrap());
+        assert_eq!(ranges(&fresh), vec![(3, 3), (13, 20)]);
+        fresh.remove(InclusiveRange::new(0, 3).unwrap());
+        assert_eq!(ranges(&fresh), vec![(13, 20)]);
+        fresh.remove(InclusiveRange::new(15, 16).unwrap());
+        assert_eq!(ranges(&fresh), vec![(13, 14), (17, 20)]);
+        fresh.remove(InclusiveRange::new(0, u64::MAX).unwrap());
+        assert!(fresh.is_empty());
+    }
+
+    #[test]
+    fn test_gaps() {
+        let fresh = set(&[(3, 5), (10, 20), (22, 22)]);
+        let gaps: Vec<_> = fresh.gaps().map(|x| (x.start(), x.end())).collect();
+        assert_eq!(gaps, vec![(6, 9), (21, 21)]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (40, 50)]);
+        assert_eq!(ranges(&a.union(&b)), vec![(0, 30), (40, 50)]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![(5, 10), (20, 25)]);
+        assert_eq!(ranges(&a.difference(&b)), vec![(0, 4), (26, 30)]);
+        assert_eq!(ranges(&b.difference(&a)), vec![(11, 19), (40, 50)]);
+    }
+
+    #[test]
+    fn test_signed() {
+        let range = |start, end| InclusiveRange::new(start, end).unwrap();
+        let mut set: IntervalSet<i32> = [range(-10, -5), range(-4, 0), range(i32::MIN, -20)]
+            .into_iter()
+            .collect();
+        let merged: Vec<_> = set.iter().map(|x| (x.start(), x.end())).collect();
+        assert_eq!(merged, vec![(i32::MIN, -20), (-10, 0)]);
+        assert!(set.contains(-7) && !set.contains(-15));
+        assert_eq!(set.gaps().next(), Some(range(-19, -11)));
+        set.remove(range(i32::MIN, -1));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&set(&[(10, 14), (3, 5), (12, 18)])).unwrap();
+        assert_eq!(json, r#"[{"start":3,"end":5},{"start":10,"end":18}]"#);
+        assert_eq!(
+            serde_json::from_str::<IntervalSet>(&json).unwrap(),
+            set(&[(3, 5), (10, 18)])
+        );
+    }
+}
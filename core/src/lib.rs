@@ -0,0 +1,18 @@
+//! The puzzle logic without any parsing or IO. It only needs `alloc`, so it also runs
+//! on constrained targets and in sandboxed WASM.
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod answer;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day7;
+pub mod day8;
+pub mod geometry;
+pub mod grid;
+pub mod interval_set;
+pub mod range;
+pub mod stats;
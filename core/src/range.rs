@@ -0,0 +1,266 @@
+use core::{
+    fmt::{self, Display},
+    num::ParseIntError,
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+/// The integers ranges can hold, see [`InclusiveRange`]
+pub trait Integer: Copy + Ord {
+    /// The integer after this one, `None` for the largest
+    fn checked_next(self) -> Option<Self>;
+    /// The integer before this one, `None` for the smallest
+    fn checked_prev(self) -> Option<Self>;
+    /// How many integers there are from `start` to `end` excluding `end`, which always fits in
+    /// a `u128` as none of these types is wider than 64 bits
+    fn distance(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn checked_next(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn checked_prev(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn distance(start: Self, end: Self) -> u128 {
+                    (start as i128).abs_diff(end as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRangeError<T = u64> {
+    NoSeparator,
+    InvalidStart(ParseIntError),
+    InvalidEnd(ParseIntError),
+    StartAfterEnd { start: T, end: T },
+}
+
+impl<T: Display> Display for ParseRangeError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSeparator => write!(f, "expected <start>-<end>"),
+            Self::InvalidStart(e) => write!(f, "failed to parse start: {e}"),
+            Self::InvalidEnd(e) => write!(f, "failed to parse end: {e}"),
+            Self::StartAfterEnd { start, end } => {
+                write!(f, "range start {start} is after its end {end}")
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug + Display> core::error::Error for ParseRangeError<T> {}
+
+/// A non empty range of IDs including both ends, `u64`s unless said otherwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "UncheckedRange<T>",
+        bound(deserialize = "T: Integer + Display + serde::Deserialize<'de>")
+    )
+)]
+pub struct InclusiveRange<T = u64> {
+    start: T,
+    end: T,
+}
+
+/// What a range deserializes from before its ends are checked
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedRange<T> {
+    start: T,
+    end: T,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Integer> TryFrom<UncheckedRange<T>> for InclusiveRange<T> {
+    type Error = ParseRangeError<T>;
+
+    fn try_from(UncheckedRange { start, end }: UncheckedRange<T>) -> Result<Self, Self::Error> {
+        Self::new(start, end).ok_or(ParseRangeError::StartAfterEnd { start, end })
+    }
+}
+
+impl<T: Integer> InclusiveRange<T> {
+    /// `None` if `start` is after `end`
+    pub fn new(start: T, end: T) -> Option<Self> {
+        if start > end {
+            return None;
+        }
+        Some(Self { start, end })
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// The number of IDs in the range, a `u128` since the full `u64` range holds 2^64 of them
+    // a range always holds at least its start, so there is no `is_empty`
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u128 {
+        T::distance(self.start, self.end) + 1
+    }
+
+    pub fn contains(&self, id: T) -> bool {
+        self.start <= id && id <= self.end
+    }
+
+    pub fn iter(&self) -> RangeInclusive<T> {
+        self.start..=self.end
+    }
+
+    /// The IDs in both ranges
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// A single range holding the IDs of both, `None` if there's a gap between them
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if first
+            .end
+            .checked_next()
+            .is_some_and(|next| next < second.start)
+        {
+            return None;
+        }
+        Some(Self {
+            start: first.start,
+            end: first.end.max(second.end),
+        })
+    }
+
+    /// Splits into the IDs before `at` and the IDs from `at` on, either side may be empty
+    pub fn split_at(&self, at: T) -> (Option<Self>, Option<Self>) {
+        let before = at
+            .checked_prev()
+            .and_then(|last| Self::new(self.start, self.end.min(last)));
+        let after = Self::new(self.start.max(at), self.end);
+        (before, after)
+    }
+}
+
+impl<T: Integer + FromStr<Err = ParseIntError>> FromStr for InclusiveRange<T> {
+    type Err = ParseRangeError<T>;
+
+    /// Parses `<start>-<end>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('-').ok_or(ParseRangeError::NoSeparator)?;
+        let start = start.parse().map_err(ParseRangeError::InvalidStart)?;
+        let end = end.parse().map_err(ParseRangeError::InvalidEnd)?;
+        Self::new(start, end).ok_or(ParseRangeError::StartAfterEnd { start, end })
+    }
+}
+
+impl<T: Display> Display for InclusiveRange<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    fn range(start: u64, end: u64) -> InclusiveRange {
+        InclusiveRange::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("3-5".parse(), Ok(range(3, 5)));
+        assert_eq!(
+            "5-3".parse::<InclusiveRange>(),
+            Err(ParseRangeError::StartAfterEnd { start: 5, end: 3 })
+        );
+        assert_eq!(
+            "5-3".parse::<InclusiveRange>().unwrap_err().to_string(),
+            "range start 5 is after its end 3"
+        );
+        assert_eq!(
+            "5".parse::<InclusiveRange>(),
+            Err(ParseRangeError::NoSeparator)
+        );
+        assert!(matches!(
+            "a-3".parse::<InclusiveRange>(),
+            Err(ParseRangeError::InvalidStart(_))
+        ));
+        assert!(matches!(
+            "1-2-3".parse::<InclusiveRange>(),
+            Err(ParseRangeError::InvalidEnd(_))
+        ));
+        assert_eq!(range(3, 5).to_string(), "3-5");
+    }
+
+    #[test]
+    fn test_len() {
+        assert_eq!(range(3, 5).len(), 3);
+        assert_eq!(range(7, 7).len(), 1);
+        assert_eq!(range(0, u64::MAX).len(), 1 << 64);
+        let signed = InclusiveRange::new(i64::MIN, i64::MAX).unwrap();
+        assert_eq!(signed.len(), 1 << 64);
+        assert_eq!(InclusiveRange::new(-3i8, 2).unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        assert_eq!(range(3, 10).intersection(&range(8, 20)), Some(range(8, 10)));
+        assert_eq!(range(3, 10).intersection(&range(11, 20)), None);
+
+        assert_eq!(range(8, 20).union(&range(3, 10)), Some(range(3, 20)));
+        assert_eq!(range(3, 10).union(&range(11, 20)), Some(range(3, 20)));
+        assert_eq!(range(3, 10).union(&range(12, 20)), None);
+        assert_eq!(range(3, 10).union(&range(4, 5)), Some(range(3, 10)));
+    }
+
+    #[test]
+    fn test_split_at() {
+        assert_eq!(
+            range(3, 10).split_at(5),
+            (Some(range(3, 4)), Some(range(5, 10)))
+        );
+        assert_eq!(range(3, 10).split_at(3), (None, Some(range(3, 10))));
+        assert_eq!(range(3, 10).split_at(11), (Some(range(3, 10)), None));
+        assert_eq!(range(0, 10).split_at(0), (None, Some(range(0, 10))));
+
+        let signed = InclusiveRange::new(i32::MIN, -1).unwrap();
+        assert_eq!(signed.split_at(i32::MIN), (None, Some(signed)));
+        assert_eq!(
+            signed.union(&InclusiveRange::new(0, 5).unwrap()),
+            InclusiveRange::new(i32::MIN, 5)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&range(3, 5)).unwrap();
+        assert_eq!(json, r#"{"start":3,"end":5}"#);
+        assert_eq!(serde_json::from_str(&json).ok(), Some(range(3, 5)));
+
+        let err = serde_json::from_str::<InclusiveRange>(r#"{"start":5,"end":3}"#).unwrap_err();
+        assert_eq!(err.to_string(), "range start 5 is after its end 3");
+    }
+}
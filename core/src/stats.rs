@@ -0,0 +1,111 @@
+//! Counters of the work the solutions do, kept for the whole process.
+//!
+//! Without the `stats` feature the counters don't exist and counting compiles to nothing.
+
+use core::fmt::{self, Display};
+#[cfg(feature = "stats")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Whether counting is compiled in
+pub const ENABLED: bool = cfg!(feature = "stats");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Counter {
+    /// Grid cells read while looking for accessible papers, neighbours included
+    CellsVisited,
+    /// IDs checked for being invalid
+    NumbersChecked,
+    /// Ranges merged into another while inserting into an `IntervalSet`
+    MergeIterations,
+    /// Distances computed between pairs of locations
+    PairDistances,
+    /// Connections that joined two separate circuits
+    Merges,
+}
+
+impl Counter {
+    pub const ALL: [Self; 5] = [
+        Self::CellsVisited,
+        Self::NumbersChecked,
+        Self::MergeIterations,
+        Self::PairDistances,
+        Self::Merges,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CellsVisited => "cells visited",
+            Self::NumbersChecked => "numbers checked",
+            Self::MergeIterations => "merge iterations",
+            Self::PairDistances => "pair distances",
+            Self::Merges => "merges",
+        }
+    }
+}
+
+#[cfg(feature = "stats")]
+static COUNTS: [AtomicU64; Counter::ALL.len()] = [const { AtomicU64::new(0) }; Counter::ALL.len()];
+
+#[inline(always)]
+pub fn add(counter: Counter, n: u64) {
+    #[cfg(feature = "stats")]
+    COUNTS[counter as usize].fetch_add(n, Ordering::Relaxed);
+    #[cfg(not(feature = "stats"))]
+    let _ = (counter, n);
+}
+
+/// Sets every counter back to zero
+pub fn reset() {
+    #[cfg(feature = "stats")]
+    for count in &COUNTS {
+        count.store(0, Ordering::Relaxed);
+    }
+}
+
+/// The counts so far, all zero without the `stats` feature
+pub fn snapshot() -> Stats {
+    #[cfg(feature = "stats")]
+    let counts = COUNTS.each_ref().map(|count| count.load(Ordering::Relaxed));
+    #[cfg(not(feature = "stats"))]
+    let counts = [0; Counter::ALL.len()];
+    Stats(counts)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats([u64; Counter::ALL.len()]);
+
+impl Stats {
+    pub fn get(&self, counter: Counter) -> u64 {
+        self.0[counter as usize]
+    }
+}
+
+/// A line for every counter that was incremented
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for counter in Counter::ALL {
+            let count = self.get(counter);
+            if count != 0 {
+                writeln!(f, "{}: {count}", counter.name())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "stats"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters() {
+        // the counters are shared with every other test, so only check they grow
+        let before = snapshot();
+        add(Counter::Merges, 3);
+        add(Counter::PairDistances, 1);
+        let after = snapshot();
+        assert!(after.get(Counter::Merges) >= before.get(Counter::Merges) + 3);
+        assert!(after.get(Counter::PairDistances) > before.get(Counter::PairDistances));
+        assert!(after.to_string().contains("merges: "));
+    }
+}
@@ -0,0 +1,107 @@
+#ifndef AOC_H
+#define AOC_H
+
+/* Generated by cbindgen from ffi/src/lib.rs, do not edit. Regenerate with `AOC_BLESS=1 cargo test -p aoc-rs-2025-ffi`. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The result of every call into the library
+ */
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  AOC_STATUS_NULL_POINTER = 1,
+  AOC_STATUS_INVALID_UTF8 = 2,
+  AOC_STATUS_UNKNOWN_PUZZLE = 3,
+  AOC_STATUS_SOLVE_FAILED = 4,
+  AOC_STATUS_TIMED_OUT = 5,
+  AOC_STATUS_PANICKED = 6,
+  AOC_STATUS_BUFFER_TOO_SMALL = 7,
+} AocStatus;
+
+/**
+ * Knobs for `aoc_solve_with_options`, start from `aoc_default_options()`
+ */
+typedef struct AocOptions {
+  /**
+   * How many of the closest junction box pairs day8 part1 connects
+   */
+  size_t num_connections;
+  /**
+   * How many numbers the day1 dial has
+   */
+  uint64_t dial_size;
+  /**
+   * Where the day1 dial points before the first rotation
+   */
+  uint64_t dial_start;
+  /**
+   * The position day1 counts landings on and visits to, instead of zero
+   */
+  uint64_t dial_target;
+  /**
+   * Give up waiting for the answer after this many milliseconds, 0 waits forever
+   */
+  uint64_t timeout_ms;
+} AocOptions;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+struct AocOptions aoc_default_options(void);
+
+/**
+ * A static NUL terminated description of the status, which is taken as an int since C
+ * lets callers pass values that aren't a status
+ */
+const char *aoc_status_message(int status);
+
+/**
+ * Solves the puzzle with the default options, see `aoc_solve_with_options`.
+ *
+ * # Safety
+ * See `aoc_solve_with_options`
+ */
+enum AocStatus aoc_solve(uint8_t day,
+                         uint8_t part,
+                         const uint8_t *input,
+                         size_t input_len,
+                         char *out,
+                         size_t out_cap,
+                         size_t *out_len);
+
+/**
+ * Solves the puzzle of `day` and `part` on the `input_len` bytes at `input`.
+ *
+ * On `AOC_STATUS_OK` the NUL terminated answer is written to `out` and its length
+ * to `out_len`. If `out_cap` can't hold the answer and its NUL, nothing is written,
+ * `AOC_STATUS_BUFFER_TOO_SMALL` is returned and `out_len` is set to the answer length
+ * so the call can be retried with a large enough buffer.
+ *
+ * On a failing solution or an unknown puzzle the error message is written to `out`
+ * instead, truncated to fit.
+ *
+ * # Safety
+ * `input` must point to `input_len` readable bytes (it may be null if `input_len` is 0),
+ * `out` must point to `out_cap` writable bytes (it may be null if `out_cap` is 0),
+ * `options` must be null or point to valid options, and `out_len` must be writable.
+ */
+enum AocStatus aoc_solve_with_options(uint8_t day,
+                                      uint8_t part,
+                                      const uint8_t *input,
+                                      size_t input_len,
+                                      const struct AocOptions *options,
+                                      char *out,
+                                      size_t out_cap,
+                                      size_t *out_len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC_H */
@@ -0,0 +1,310 @@
+//! A C API over the solutions. The matching header is `include/aoc.h`.
+
+use std::{
+    ffi::{c_char, c_int},
+    ptr, slice,
+    time::Duration,
+};
+
+use aoc_rs_2025::runner::{self, Params, SolveError};
+
+/// The result of every call into the library
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    UnknownPuzzle = 3,
+    SolveFailed = 4,
+    TimedOut = 5,
+    Panicked = 6,
+    BufferTooSmall = 7,
+}
+
+/// Knobs for `aoc_solve_with_options`, start from `aoc_default_options()`
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct AocOptions {
+    /// How many of the closest junction box pairs day8 part1 connects
+    pub num_connections: usize,
+    /// How many numbers the day1 dial has
+    pub dial_size: u64,
+    /// Where the day1 dial points before the first rotation
+    pub dial_start: u64,
+    /// The position day1 counts landings on and visits to, instead of zero
+    pub dial_target: u64,
+    /// Give up waiting for the answer after this many milliseconds, 0 waits forever
+    pub timeout_ms: u64,
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn aoc_default_options() -> AocOptions {
+    let params = Params::default();
+    AocOptions {
+        num_connections: params.num_connections,
+        dial_size: params.dial_size,
+        dial_start: params.dial_start,
+        dial_target: params.dial_target,
+        timeout_ms: 0,
+    }
+}
+
+/// A static NUL terminated description of the status, which is taken as an int since C
+/// lets callers pass values that aren't a status
+#[unsafe(no_mangle)]
+pub extern "C" fn aoc_status_message(status: c_int) -> *const c_char {
+    // the discriminants of `AocStatus`
+    let message = match status {
+        0 => c"ok",
+        1 => c"a required pointer is null",
+        2 => c"input is not valid utf-8",
+        3 => c"there is no solution for this day and part",
+        4 => c"the solution failed",
+        5 => c"the solution timed out",
+        6 => c"the solution panicked",
+        7 => c"the output buffer is too small",
+        _ => c"unknown status",
+    };
+    message.as_ptr()
+}
+
+/// Copies `s` and a NUL into `out`, truncating `s` if it doesn't fit.
+///
+/// # Safety
+/// `out` must point to `out_cap` writable bytes, or be null if `out_cap` is 0
+unsafe fn write_truncated(s: &str, out: *mut c_char, out_cap: usize) -> usize {
+    if out_cap == 0 {
+        return 0;
+    }
+    // cutting a char in half would leave invalid utf-8
+    let mut len = s.len().min(out_cap - 1);
+    while !s.is_char_boundary(len) {
+        len -= 1;
+    }
+    // SAFETY: `len + 1 <= out_cap`, and the caller promises `out` holds `out_cap` bytes
+    unsafe {
+        ptr::copy_nonoverlapping(s.as_ptr().cast::<c_char>(), out, len);
+        *out.add(len) = 0;
+    }
+    len
+}
+
+/// Solves the puzzle with the default options, see `aoc_solve_with_options`.
+///
+/// # Safety
+/// See `aoc_solve_with_options`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    out: *mut c_char,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> AocStatus {
+    let options = aoc_default_options();
+    // SAFETY: forwarded as is from our caller
+    unsafe { aoc_solve_with_options(day, part, input, input_len, &options, out, out_cap, out_len) }
+}
+
+/// Solves the puzzle of `day` and `part` on the `input_len` bytes at `input`.
+///
+/// On `AOC_STATUS_OK` the NUL terminated answer is written to `out` and its length
+/// to `out_len`. If `out_cap` can't hold the answer and its NUL, nothing is written,
+/// `AOC_STATUS_BUFFER_TOO_SMALL` is returned and `out_len` is set to the answer length
+/// so the call can be retried with a large enough buffer.
+///
+/// On a failing solution or an unknown puzzle the error message is written to `out`
+/// instead, truncated to fit.
+///
+/// # Safety
+/// `input` must point to `input_len` readable bytes (it may be null if `input_len` is 0),
+/// `out` must point to `out_cap` writable bytes (it may be null if `out_cap` is 0),
+/// `options` must be null or point to valid options, and `out_len` must be writable.
+#[unsafe(no_mangle)]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn aoc_solve_with_options(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    options: *const AocOptions,
+    out: *mut c_char,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> AocStatus {
+    if out_len.is_null() || (input.is_null() && input_len != 0) || (out.is_null() && out_cap != 0) {
+        return AocStatus::NullPointer;
+    }
+
+    let input = if input_len == 0 {
+        &[]
+    } else {
+        // SAFETY: not null, and the caller promises it holds `input_len` bytes
+        unsafe { slice::from_raw_parts(input, input_len) }
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return AocStatus::InvalidUtf8;
+    };
+    // SAFETY: the caller promises it's either null or valid
+    let options = unsafe { options.as_ref() }
+        .copied()
+        .unwrap_or_else(|| aoc_default_options());
+
+    let params = Params {
+        num_connections: options.num_connections,
+        dial_size: options.dial_size,
+        dial_start: options.dial_start,
+        dial_target: options.dial_target,
+    };
+    let timeout = (options.timeout_ms != 0).then(|| Duration::from_millis(options.timeout_ms));
+    let result = runner::solve_with_timeout(day, part, input.to_string(), params, timeout);
+
+    let (status, message) = match result {
+        Ok(solution) => {
+            // SAFETY: checked it's not null above
+            unsafe { *out_len = solution.answer.len() };
+            if solution.answer.len() >= out_cap {
+                return AocStatus::BufferTooSmall;
+            }
+            // SAFETY: the caller promises `out` holds `out_cap` bytes
+            unsafe { write_truncated(&solution.answer, out, out_cap) };
+            return AocStatus::Ok;
+        }
+        Err(e @ SolveError::UnknownPuzzle { .. }) => (AocStatus::UnknownPuzzle, e.to_string()),
+        Err(e @ SolveError::TimedOut(_)) => (AocStatus::TimedOut, e.to_string()),
+        Err(e @ SolveError::Panicked) => (AocStatus::Panicked, e.to_string()),
+        Err(e @ SolveError::Failed(_)) => (AocStatus::SolveFailed, e.to_string()),
+    };
+
+    // SAFETY: the caller promises `out` holds `out_cap` bytes, and `out_len` was checked above
+    unsafe { *out_len = write_truncated(&message, out, out_cap) };
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    fn solve(day: u8, part: u8, input: &str, out: &mut [u8]) -> (AocStatus, String) {
+        let mut out_len = 0;
+        let status = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr().cast(),
+                out.len(),
+                &mut out_len,
+            )
+        };
+        let written = out
+            .get(..out_len)
+            .map(|x| String::from_utf8_lossy(x).into_owned())
+            .unwrap_or_default();
+        (status, written)
+    }
+
+    #[test]
+    fn test_solve() {
+        let mut out = [0; 32];
+        assert_eq!(
+            solve(2, 1, "11-22", &mut out),
+            (AocStatus::Ok, "33".to_string())
+        );
+        assert_eq!(out[2], 0);
+    }
+
+    #[test]
+    fn test_dial_options() {
+        let input = "L68\nL30\nR48\n";
+        let options = AocOptions {
+            dial_size: 10,
+            dial_start: 5,
+            dial_target: 7,
+            ..aoc_default_options()
+        };
+        let mut out = [0u8; 64];
+        let mut out_len = 0;
+        let solve = |options: &AocOptions, out: &mut [u8], out_len: &mut usize| unsafe {
+            aoc_solve_with_options(
+                1,
+                1,
+                input.as_ptr(),
+                input.len(),
+                options,
+                out.as_mut_ptr().cast(),
+                out.len(),
+                out_len,
+            )
+        };
+        // 5 -> 7 -> 7 -> 5 on a dial of 10
+        assert_eq!(solve(&options, &mut out, &mut out_len), AocStatus::Ok);
+        assert_eq!(&out[..out_len], b"2");
+
+        let options = AocOptions {
+            dial_size: 0,
+            ..aoc_default_options()
+        };
+        assert_eq!(
+            solve(&options, &mut out, &mut out_len),
+            AocStatus::SolveFailed
+        );
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        let mut out = [0; 8];
+        assert_eq!(
+            solve(1, 1, "X1", &mut out),
+            (AocStatus::SolveFailed, "failed ".to_string())
+        );
+        assert_eq!(solve(0, 1, "", &mut out).0, AocStatus::UnknownPuzzle);
+
+        let mut out = [0; 2];
+        let mut out_len = 0;
+        let status = unsafe {
+            aoc_solve(
+                2,
+                1,
+                c"11-22".as_ptr().cast(),
+                5,
+                out.as_mut_ptr(),
+                2,
+                &mut out_len,
+            )
+        };
+        assert_eq!(status, AocStatus::BufferTooSmall);
+        assert_eq!(out_len, 2);
+
+        let status = unsafe { aoc_solve(1, 1, ptr::null(), 1, out.as_mut_ptr(), 2, &mut out_len) };
+        assert_eq!(status, AocStatus::NullPointer);
+    }
+
+    #[test]
+    fn test_status_message() {
+        let message = unsafe { CStr::from_ptr(aoc_status_message(AocStatus::TimedOut as c_int)) };
+        assert_eq!(message, c"the solution timed out");
+        let message =
+            unsafe { CStr::from_ptr(aoc_status_message(AocStatus::BufferTooSmall as c_int)) };
+        assert_eq!(message, c"the output buffer is too small");
+        for status in [-1, 8, c_int::MAX] {
+            let message = unsafe { CStr::from_ptr(aoc_status_message(status)) };
+            assert_eq!(message, c"unknown status");
+        }
+    }
+
+    #[test]
+    fn test_write_truncated() {
+        let mut out = [0x7f_u8; 4];
+        // "é" is 2 bytes and only 1 fits after "ab"
+        let len = unsafe { write_truncated("abé", out.as_mut_ptr().cast(), out.len()) };
+        assert_eq!(len, 2);
+        assert_eq!(out[..3], *b"ab\0");
+    }
+}
@@ -0,0 +1,74 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+#define CHECK(cond)                                                            \
+    do {                                                                       \
+        if (!(cond)) {                                                         \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__,   \
+                    #cond);                                                    \
+            return 1;                                                          \
+        }                                                                      \
+    } while (0)
+
+static AocStatus solve(uint8_t day, uint8_t part, const char *input,
+                       const AocOptions *options, char *out, size_t out_cap,
+                       size_t *out_len) {
+    return aoc_solve_with_options(day, part, (const uint8_t *)input,
+                                  strlen(input), options, out, out_cap,
+                                  out_len);
+}
+
+int main(void) {
+    const char *rotations = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+    char out[128];
+    size_t out_len = 0;
+
+    CHECK(aoc_solve(1, 2, (const uint8_t *)rotations, strlen(rotations), out,
+                    sizeof(out), &out_len) == AOC_STATUS_OK);
+    CHECK(strcmp(out, "6") == 0);
+    CHECK(out_len == 1);
+
+    /* the answer is "33", which needs 3 bytes with its NUL */
+    char small[2];
+    CHECK(solve(2, 1, "11-22", NULL, small, sizeof(small), &out_len) ==
+          AOC_STATUS_BUFFER_TOO_SMALL);
+    CHECK(out_len == 2);
+    CHECK(solve(2, 1, "11-22", NULL, NULL, 0, &out_len) ==
+          AOC_STATUS_BUFFER_TOO_SMALL);
+
+    AocOptions options = aoc_default_options();
+    options.num_connections = 1;
+    CHECK(solve(8, 1, "0,0,0\n1,1,1\n5,5,5\n", &options, out, sizeof(out),
+                &out_len) == AOC_STATUS_OK);
+    CHECK(strcmp(out, "2") == 0);
+
+    /* the example on a dial of 10 starting at 5, landing on 7 twice */
+    AocOptions dial = aoc_default_options();
+    dial.dial_size = 10;
+    dial.dial_start = 5;
+    dial.dial_target = 7;
+    CHECK(solve(1, 1, "L68\nL30\nR48\n", &dial, out, sizeof(out),
+                &out_len) == AOC_STATUS_OK);
+    CHECK(strcmp(out, "2") == 0);
+
+    options.timeout_ms = 1;
+    CHECK(solve(2, 1, "1-999999999999", &options, out, sizeof(out),
+                &out_len) == AOC_STATUS_TIMED_OUT);
+
+    CHECK(solve(1, 1, "X1", NULL, out, sizeof(out), &out_len) ==
+          AOC_STATUS_SOLVE_FAILED);
+    CHECK(strcmp(out, "failed to parse rotations: line 1, column 1: "
+                 "expected 'L' or 'R', found 'X'") == 0);
+    CHECK(solve(9, 1, "", NULL, out, sizeof(out), &out_len) ==
+          AOC_STATUS_UNKNOWN_PUZZLE);
+    CHECK(aoc_solve(1, 1, NULL, 1, out, sizeof(out), &out_len) ==
+          AOC_STATUS_NULL_POINTER);
+
+    CHECK(strcmp(aoc_status_message(AOC_STATUS_INVALID_UTF8),
+                 "input is not valid utf-8") == 0);
+    CHECK(strcmp(aoc_status_message(42), "unknown status") == 0);
+
+    return 0;
+}
@@ -0,0 +1,55 @@
+use std::{env, fs, path::Path, process::Command};
+
+const CRATE_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
+#[test]
+fn test_header_is_up_to_date() {
+    let config = cbindgen::Config::from_file(format!("{CRATE_DIR}/cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{CRATE_DIR}/src/lib.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut generated);
+
+    let header = Path::new(CRATE_DIR).join("include/aoc.h");
+    if env::var_os("AOC_BLESS").is_some() {
+        fs::write(&header, &generated).unwrap();
+    }
+    let existing = fs::read(&header).unwrap();
+    assert!(
+        existing == generated,
+        "include/aoc.h is out of date, rerun with AOC_BLESS=1"
+    );
+}
+
+#[test]
+fn test_c_program() {
+    // integration tests live next to the cdylib in `target/<profile>/deps`
+    let test_exe = env::current_exe().unwrap();
+    let lib_dir = test_exe.parent().unwrap();
+    let program = lib_dir.join("aoc_ffi_c_test");
+
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(cc)
+        .args(["-std=c11", "-Wall", "-Wextra", "-Werror", "-I"])
+        .arg(Path::new(CRATE_DIR).join("include"))
+        .arg(Path::new(CRATE_DIR).join("tests/c/solve.c"))
+        .arg("-L")
+        .arg(lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-laoc_ffi")
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success(), "failed to compile the C test program");
+
+    // cargo puts `target/<profile>` first, where the cdylib may be an older build
+    let status = Command::new(&program)
+        .env("LD_LIBRARY_PATH", lib_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "the C test program failed");
+}
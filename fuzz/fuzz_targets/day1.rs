@@ -0,0 +1,27 @@
+#![no_main]
+
+use aoc_rs_2025::{day1, runner::Params};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let dial = Params::default().dial().unwrap();
+    let part1 = day1::part1(input, dial);
+    let part2 = day1::part2(input, dial);
+
+    // streaming takes the same input, with the same answers and errors
+    let tally = day1::tally(input.as_bytes(), dial, 0);
+    match (&tally, &part1, &part2) {
+        (Ok(tally), Ok(part1), Ok(part2)) => {
+            assert_eq!(tally.landings(), part1);
+            assert_eq!(tally.visits(), part2);
+        }
+        (Err(streamed), Err(e), Err(_)) => assert_eq!(format!("{streamed:#}"), format!("{e:#}")),
+        results => panic!("streaming disagrees: {results:?}"),
+    }
+
+    // printing the rotations gives an input with the same rotations
+    if let Ok(rotations) = day1::parse_rotations(input) {
+        let printed: String = rotations.iter().map(|rotation| format!("{rotation}\n")).collect();
+        assert_eq!(day1::parse_rotations(&printed).unwrap(), rotations);
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc_rs_2025::{day1, runner::Params};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let dial = Params::default().dial().unwrap();
+    let Ok(lock) = day1::open_lock(input, dial) else {
+        return;
+    };
+    assert!(!lock.dials().is_empty());
+    assert_eq!(lock.dials().len(), lock.counts().len());
+});
@@ -0,0 +1,26 @@
+#![no_main]
+
+use aoc_core::day1::Crossings;
+use aoc_rs_2025::{day1, runner::Params};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let dial = Params::default().dial().unwrap();
+    let Ok(targets) = day1::parse_targets(input) else {
+        return;
+    };
+    for crossings in [Crossings::Any, Crossings::Fewest, Crossings::Most] {
+        let Ok(plan) = day1::plan_rotations(input, dial, crossings) else {
+            assert!(targets.iter().any(|target| *target >= dial.size()));
+            continue;
+        };
+        // the plan is a puzzle input again, with a rotation per target
+        let rotations = day1::parse_rotations(&plan).unwrap();
+        assert_eq!(rotations.len(), targets.len());
+        let end = rotations.iter().fold(dial, |mut dial, rotation| {
+            dial += *rotation;
+            dial
+        });
+        assert_eq!(targets.last().map_or(dial.position(), |last| *last), end.position());
+    }
+});
@@ -0,0 +1,18 @@
+#![no_main]
+
+use aoc_rs_2025::day2::IdRange;
+use libfuzzer_sys::fuzz_target;
+
+/// Summing walks every ID in the range, so huge ranges would only time out
+const MAX_RANGE_LEN: u128 = 100_000;
+
+fuzz_target!(|input: &str| {
+    let Ok(range) = input.parse::<IdRange>() else {
+        return;
+    };
+    if range.len() > MAX_RANGE_LEN {
+        return;
+    }
+    let _ = range.sum_invalid_ids();
+    let _ = range.sum_invalid_ids_part2();
+});
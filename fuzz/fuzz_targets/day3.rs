@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc_rs_2025::day3::{self, PowerBank};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(power_bank) = input.parse::<PowerBank>() {
+        let _ = power_bank.sum_top_2();
+        let _ = power_bank.sum_top_12();
+    }
+    let _ = day3::part1(input);
+    let _ = day3::part2(input);
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_rs_2025::day4::{self, Grid};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if input.parse::<Grid>().is_err() {
+        return;
+    }
+    let _ = day4::part1(input);
+    let _ = day4::part2(input);
+});
@@ -0,0 +1,25 @@
+#![no_main]
+
+use aoc_core::range::InclusiveRange;
+use aoc_rs_2025::day5::{self, IngredientDatabase};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(range) = input.parse::<InclusiveRange>() {
+        assert_eq!(range.to_string().parse(), Ok(range));
+    }
+
+    let Ok(database) = input.parse::<IngredientDatabase>() else {
+        return;
+    };
+    // merging keeps exactly the IDs of the ranges as given
+    for &id in database.ingredients() {
+        let in_range = database
+            .ingredient_id_ranges()
+            .iter()
+            .any(|range| range.contains(id));
+        assert_eq!(database.is_fresh(&id), in_range);
+    }
+    let _ = day5::part1(input);
+    let _ = day5::part2(input);
+});
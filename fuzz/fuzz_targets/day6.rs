@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc_rs_2025::day6;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day6::parse_problems(input);
+    let _ = day6::parse_problems_part2(input);
+    let _ = day6::part1(input);
+    let _ = day6::part2(input);
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_rs_2025::day7::{self, Manifold};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if input.parse::<Manifold>().is_err() {
+        return;
+    }
+    let _ = day7::part1(input);
+    let _ = day7::part2(input);
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use aoc_rs_2025::day8::{self, JunctionBox};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = input.parse::<JunctionBox>();
+    let _ = day8::part1(input, 10);
+    let _ = day8::part2(input);
+});
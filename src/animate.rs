@@ -0,0 +1,201 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use anyhow::Context;
+use aoc_core::{
+    day4::Cell,
+    day7::{Location, ManifoldWalker},
+};
+
+use crate::{day4, day7};
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+    pub frames_per_second: u32,
+    /// Where every frame is also written as `frame-<n>.txt`
+    pub frames_dir: Option<PathBuf>,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            frames_per_second: 10,
+            frames_dir: None,
+        }
+    }
+}
+
+/// The grid before every round, the papers about to be removed marked with `x`,
+/// and the grid once nothing is accessible anymore
+fn day4_frames(grid: &mut day4::Grid) -> Vec<String> {
+    let mut frames = Vec::new();
+    loop {
+        let accessible = grid.get_accessible_papers();
+        let mut chars = grid.cells().map(|cell| match cell {
+            Cell::Paper => '@',
+            Cell::Nothing => '.',
+        });
+        for at in &accessible {
+            chars[*at] = 'x';
+        }
+        frames.push(chars.display(|c| *c).to_string());
+
+        if accessible.is_empty() {
+            return frames;
+        }
+        grid.remove_papers(&accessible);
+    }
+}
+
+/// The manifold after every step, with every location a beam went through marked with `|`
+fn day7_frames(manifold: &day7::Manifold) -> Vec<String> {
+    let mut chars = manifold.grid().map(|location| match location {
+        Location::Empty => '.',
+        Location::Splitter => '^',
+    });
+    chars[manifold.start()] = 'S';
+
+    let mut frames = vec![chars.display(|c| *c).to_string()];
+    let mut walker = ManifoldWalker::new(manifold);
+    while walker.step().is_some() {
+        for beam in walker.beams() {
+            chars[*beam] = '|';
+        }
+        frames.push(chars.display(|c| *c).to_string());
+    }
+    frames
+}
+
+/// The frames of the given day, as plain text
+pub fn frames(day: u8, input: &str) -> anyhow::Result<Vec<String>> {
+    match day {
+        4 => {
+            let mut grid: day4::Grid = input.parse().context("failed to parse grid")?;
+            Ok(day4_frames(&mut grid))
+        }
+        7 => {
+            let manifold: day7::Manifold = input.parse().context("failed to parse input")?;
+            Ok(day7_frames(&manifold))
+        }
+        day => anyhow::bail!("day {day} can't be animated, only days 4 and 7 can"),
+    }
+}
+
+/// Highlights the changing parts of a frame
+fn colorize(frame: &str) -> String {
+    let mut colored = String::with_capacity(frame.len());
+    for c in frame.chars() {
+        match c {
+            'x' => colored.extend([RED, "x", RESET]),
+            '|' => colored.extend([YELLOW, "|", RESET]),
+            c => colored.push(c),
+        }
+    }
+    colored
+}
+
+/// Draws every frame over the previous one, waiting between them to keep the frame rate
+pub fn play(frames: &[String], frames_per_second: u32, out: &mut impl Write) -> anyhow::Result<()> {
+    let delay = Duration::from_secs(1) / frames_per_second.max(1);
+    write!(out, "{CLEAR_SCREEN}")?;
+    for frame in frames {
+        write!(out, "{CURSOR_HOME}{}", colorize(frame))?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
+    Ok(())
+}
+
+pub fn write_frames(frames: &[String], dir: &Path) -> anyhow::Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    for (i, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!("frame-{i:04}.txt"));
+        std::fs::write(&path, frame)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Plays the animation of the given day, and writes its frames if asked to
+pub fn animate(
+    day: u8,
+    input: &str,
+    animation: &Animation,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let frames = frames(day, input)?;
+    if let Some(dir) = &animation.frames_dir {
+        write_frames(&frames, dir)?;
+    }
+    play(&frames, animation.frames_per_second, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_day4_frames() {
+        let grid = indoc! {"
+            @@@
+            @@@
+            @.@
+        "};
+        let grid_frames = frames(4, grid).unwrap();
+        assert_eq!(
+            grid_frames,
+            ["x@x\n@@@\nx.x\n", ".x.\nxxx\n...\n", "...\n...\n...\n"]
+        );
+    }
+
+    #[test]
+    fn test_day7_frames() {
+        let manifold = indoc! {"
+            .S.
+            ...
+            .^.
+            ...
+        "};
+        let manifold_frames = frames(7, manifold).unwrap();
+        assert_eq!(
+            manifold_frames,
+            [
+                ".S.\n...\n.^.\n...\n",
+                ".S.\n.|.\n.^.\n...\n",
+                ".S.\n.|.\n|^|\n...\n",
+                ".S.\n.|.\n|^|\n|.|\n"
+            ]
+        );
+        assert!(frames(1, "L1").is_err());
+    }
+
+    #[test]
+    fn test_play_and_write_frames() {
+        let frames = vec!["x.\n".to_string(), "..\n".to_string()];
+        let mut out = Vec::new();
+        play(&frames, 1000, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[2J\x1b[H\x1b[31mx\x1b[0m.\n\x1b[H..\n"
+        );
+
+        let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        write_frames(&frames, &dir).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("frame-0001.txt")).unwrap(),
+            "..\n"
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
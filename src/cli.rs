@@ -0,0 +1,303 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use aoc_core::day1::{Crossings, ZeroCount};
+use aoc_rs_2025::{animate::Animation, dump::Stage, runner::Params};
+
+pub const USAGE: &str = "\
+usage:
+    aoc-rs-2025 run --day <day> --part <part> [--timeout-ms <ms>] [--num-connections <n>] [--stats]
+        [--dial-size <n>] [--dial-start <n>] [--dial-target <n>] [--stream] [--explain]
+        [--animate [--fps <n>] [--frames-dir <dir>]] < input
+    aoc-rs-2025 serve [--port <port>] [--timeout-ms <ms>]
+    aoc-rs-2025 dump --day <day> --stage <parsed|intermediate> [--num-connections <n>]
+        [--dial-size <n>] [--dial-start <n>] < input
+    aoc-rs-2025 render --day <4|7|8> [--num-connections <n>] < input > image.svg
+    aoc-rs-2025 steps [--dial-size <n>] [--dial-start <n>] [--histogram] < day1-input > steps.csv
+    aoc-rs-2025 lock [--dial-size <n>] [--dial-start <n>] < lock-input
+    aoc-rs-2025 starts --part <part> --count <n> [--dial-size <n>] < day1-input
+    aoc-rs-2025 plan [--dial-size <n>] [--dial-start <n>] [--crossings <any|fewest|most>]
+        < targets > day1-input";
+
+#[derive(Debug)]
+pub enum Command {
+    Run {
+        day: u8,
+        part: u8,
+        params: Params,
+        timeout: Option<Duration>,
+        /// Days 4 and 7 are animated before they are solved
+        animation: Option<Animation>,
+        /// Report the work counters after solving
+        stats: bool,
+        /// Solve day1 while reading its input, instead of reading it all first
+        stream: bool,
+        /// Explain every day1 rotation before solving
+        explain: bool,
+    },
+    Serve {
+        port: u16,
+        timeout: Option<Duration>,
+    },
+    Dump {
+        day: u8,
+        stage: Stage,
+        params: Params,
+    },
+    Render {
+        day: u8,
+        params: Params,
+    },
+    /// Every day1 rotation as CSV
+    Steps {
+        params: Params,
+        /// How often every position was visited instead
+        histogram: bool,
+    },
+    /// The zero counts of every dial of a day1 lock with several geared dials
+    Lock {
+        params: Params,
+    },
+    /// The day1 starting positions that give an answer of `count`
+    Starts {
+        counted: ZeroCount,
+        count: u64,
+        params: Params,
+    },
+    /// A day1 input that takes the dial to a list of positions in the fewest clicks, or with
+    /// the fewest or most zero crossings
+    Plan {
+        crossings: Crossings,
+        params: Params,
+    },
+}
+
+fn parse_value<T: std::str::FromStr>(
+    flag: &str,
+    args: &mut impl Iterator<Item = String>,
+) -> anyhow::Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let value = args
+        .next()
+        .with_context(|| format!("{flag} needs a value"))?;
+    value
+        .parse()
+        .with_context(|| format!("invalid value for {flag}: {value}"))
+}
+
+pub fn parse_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Command> {
+    let command = args.next().context("no command given")?;
+
+    let mut day = None;
+    let mut part = None;
+    let mut stage = None;
+    let mut port = 8080;
+    let mut timeout = None;
+    let mut params = Params::default();
+    let mut animate = false;
+    let mut stats = false;
+    let mut histogram = false;
+    let mut stream = false;
+    let mut explain = false;
+    let mut count = None;
+    let mut crossings = Crossings::Any;
+    let mut animation = Animation::default();
+    while let Some(flag) = args.next() {
+        match (command.as_str(), flag.as_str()) {
+            ("run" | "dump" | "render", "--day") => day = Some(parse_value(&flag, &mut args)?),
+            ("run" | "starts", "--part") => part = Some(parse_value(&flag, &mut args)?),
+            ("starts", "--count") => count = Some(parse_value(&flag, &mut args)?),
+            ("plan", "--crossings") => {
+                let value: String = parse_value(&flag, &mut args)?;
+                crossings = match value.as_str() {
+                    "any" => Crossings::Any,
+                    "fewest" => Crossings::Fewest,
+                    "most" => Crossings::Most,
+                    other => anyhow::bail!("invalid value for --crossings: {other}"),
+                }
+            }
+            ("run", "--animate") => animate = true,
+            ("run", "--stats") => stats = true,
+            ("run", "--stream") => stream = true,
+            ("run", "--explain") => explain = true,
+            ("run", "--fps") => {
+                animation.frames_per_second = parse_value(&flag, &mut args)?;
+                anyhow::ensure!(animation.frames_per_second > 0, "--fps must be positive");
+            }
+            ("run", "--frames-dir") => animation.frames_dir = Some(parse_value(&flag, &mut args)?),
+            ("dump", "--stage") => stage = Some(parse_value(&flag, &mut args)?),
+            ("run" | "dump" | "render", "--num-connections") => {
+                params.num_connections = parse_value(&flag, &mut args)?
+            }
+            ("run" | "dump" | "steps" | "lock" | "starts" | "plan", "--dial-size") => {
+                params.dial_size = parse_value(&flag, &mut args)?
+            }
+            ("run" | "dump" | "steps" | "lock" | "plan", "--dial-start") => {
+                params.dial_start = parse_value(&flag, &mut args)?
+            }
+            ("run", "--dial-target") => params.dial_target = parse_value(&flag, &mut args)?,
+            ("steps", "--histogram") => histogram = true,
+            ("serve", "--port") => port = parse_value(&flag, &mut args)?,
+            ("run" | "serve", "--timeout-ms") => {
+                timeout = Some(Duration::from_millis(parse_value(&flag, &mut args)?))
+            }
+            (command, flag) => anyhow::bail!("unknown flag for {command}: {flag}"),
+        }
+    }
+
+    if !animate && animation != Animation::default() {
+        anyhow::bail!("--fps and --frames-dir need --animate");
+    }
+    if stream && (animate || explain || timeout.is_some()) {
+        anyhow::bail!("--stream can't be combined with --animate, --explain or --timeout-ms");
+    }
+
+    match command.as_str() {
+        "run" => Ok(Command::Run {
+            day: day.context("--day is required")?,
+            part: part.context("--part is required")?,
+            params,
+            timeout,
+            animation: animate.then_some(animation),
+            stats,
+            stream,
+            explain,
+        }),
+        "serve" => Ok(Command::Serve { port, timeout }),
+        "dump" => Ok(Command::Dump {
+            day: day.context("--day is required")?,
+            stage: stage.context("--stage is required")?,
+            params,
+        }),
+        "render" => Ok(Command::Render {
+            day: day.context("--day is required")?,
+            params,
+        }),
+        "steps" => Ok(Command::Steps { params, histogram }),
+        "lock" => Ok(Command::Lock { params }),
+        "starts" => Ok(Command::Starts {
+            counted: match part.context("--part is required")? {
+                1 => ZeroCount::Landings,
+                2 => ZeroCount::Visits,
+                part => anyhow::bail!("there is no part {part}, it must be 1 or 2"),
+            },
+            count: count.context("--count is required")?,
+            params,
+        }),
+        "plan" => Ok(Command::Plan { crossings, params }),
+        other => anyhow::bail!("unknown command: {other}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> anyhow::Result<Command> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let Command::Run {
+            day,
+            part,
+            params,
+            timeout,
+            animation,
+            stats,
+            stream,
+            explain,
+        } = parse("run --day 8 --part 1 --num-connections 10 --timeout-ms 500 --stats").unwrap()
+        else {
+            panic!("expected run");
+        };
+        assert_eq!((day, part), (8, 1));
+        assert_eq!(params.num_connections, 10);
+        assert_eq!(timeout, Some(Duration::from_millis(500)));
+        assert_eq!(animation, None);
+        assert!(stats);
+        assert!(!stream && !explain);
+
+        let Command::Run { animation, .. } =
+            parse("run --day 4 --part 2 --animate --fps 30 --frames-dir frames").unwrap()
+        else {
+            panic!("expected run");
+        };
+        let animation = animation.unwrap();
+        assert_eq!(animation.frames_per_second, 30);
+        assert_eq!(animation.frames_dir, Some("frames".into()));
+
+        let Command::Serve { port, timeout } = parse("serve").unwrap() else {
+            panic!("expected serve");
+        };
+        assert_eq!(port, 8080);
+        assert_eq!(timeout, None);
+
+        let Command::Dump { day, stage, .. } = parse("dump --day 5 --stage intermediate").unwrap()
+        else {
+            panic!("expected dump");
+        };
+        assert_eq!((day, stage), (5, Stage::Intermediate));
+
+        let Command::Render { day, params } = parse("render --day 8 --num-connections 10").unwrap()
+        else {
+            panic!("expected render");
+        };
+        assert_eq!(day, 8);
+        assert_eq!(params.num_connections, 10);
+
+        let Command::Steps { params, histogram } =
+            parse("steps --dial-size 10 --dial-start 3 --histogram").unwrap()
+        else {
+            panic!("expected steps");
+        };
+        assert_eq!((params.dial_size, params.dial_start), (10, 3));
+        assert!(histogram);
+
+        let Command::Lock { params } = parse("lock --dial-size 10").unwrap() else {
+            panic!("expected lock");
+        };
+        assert_eq!(params.dial_size, 10);
+
+        let Command::Starts { counted, count, .. } =
+            parse("starts --part 2 --count 7 --dial-size 10").unwrap()
+        else {
+            panic!("expected starts");
+        };
+        assert_eq!((counted, count), (ZeroCount::Visits, 7));
+
+        let Command::Plan { crossings, .. } = parse("plan --crossings most").unwrap() else {
+            panic!("expected plan");
+        };
+        assert_eq!(crossings, Crossings::Most);
+        let Command::Plan { crossings, .. } = parse("plan --crossings any").unwrap() else {
+            panic!("expected plan");
+        };
+        assert_eq!(crossings, Crossings::Any);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("run --day 1").is_err());
+        assert!(parse("run --day one --part 1").is_err());
+        assert!(parse("serve --day 1").is_err());
+        assert!(parse("serve --port").is_err());
+        assert!(parse("fly").is_err());
+        assert!(parse("dump --day 5").is_err());
+        assert!(parse("dump --day 5 --stage solved").is_err());
+        assert!(parse("dump --day 5 --stage parsed --part 1").is_err());
+        assert!(parse("render").is_err());
+        assert!(parse("steps --day 1").is_err());
+        assert!(parse("run --day 4 --part 2 --fps 30").is_err());
+        assert!(parse("run --day 4 --part 2 --animate --fps 0").is_err());
+        assert!(parse("run --day 1 --part 2 --stream --timeout-ms 5").is_err());
+        assert!(parse("run --day 1 --part 2 --stream --explain").is_err());
+        assert!(parse("starts --part 3 --count 1").is_err());
+        assert!(parse("starts --part 1").is_err());
+        assert!(parse("plan --crossings some").is_err());
+    }
+}
@@ -1,125 +1,644 @@
+use std::{
+    io::{BufRead, Write},
+    iter,
+};
+
 use anyhow::Context;
+use aoc_core::{
+    answer::Answer,
+    day1::{
+        Crossings, Dial, Direction, Gear, Lock, Rotation, Step, ZeroCount, plan, starts_with_count,
+        visit_counts,
+    },
+    interval_set::IntervalSet,
+};
+
+use crate::parse::{ParseError, Parser, int, line, lines, many, parse_all, tag, uint, word};
+
+/// `L<distance>` or `R<distance>`, as `Rotation::from_str` reads it
+fn rotation<'a>() -> impl Parser<'a, Rotation> {
+    // an empty word still goes to `from_str`, which says what a rotation looks like
+    word().or(tag("")).try_map(str::parse::<Rotation>)
+}
+
+/// Every rotation of the input, in order
+pub fn parse_rotations(input: &str) -> anyhow::Result<Vec<Rotation>> {
+    // blank lines at the end would otherwise be rotations missing their direction
+    let input = input.trim_end_matches(['\n', '\r']);
+    parse_all(lines(rotation()), input).context("failed to parse rotations")
+}
+
+fn ensure_on_dial(dial: Dial, position: u64) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        position < dial.size(),
+        "position {position} isn't on a dial of size {}",
+        dial.size()
+    );
+    Ok(())
+}
+
+/// How many rotations leave `dial` at `target`
+pub fn landings(input: &str, dial: Dial, target: u64) -> anyhow::Result<Answer> {
+    ensure_on_dial(dial, target)?;
+    let num_landings =
+        dial.steps(parse_rotations(input)?)
+            .fold(Answer::ZERO, |mut num_landings, step| {
+                if step.end == target {
+                    num_landings += Answer::ONE;
+                }
+                num_landings
+            });
+    Ok(num_landings)
+}
+
+/// How many clicks leave `dial` at `target`
+pub fn visits(input: &str, dial: Dial, target: u64) -> anyhow::Result<Answer> {
+    ensure_on_dial(dial, target)?;
+    let num_visits =
+        dial.steps(parse_rotations(input)?)
+            .fold(Answer::ZERO, |mut num_visits, step| {
+                num_visits += step.visits(target).into();
+                num_visits
+            });
+    Ok(num_visits)
+}
+
+/// How many rotations leave `dial` at zero
+pub fn part1(input: &str, dial: Dial) -> anyhow::Result<Answer> {
+    landings(input, dial, 0)
+}
+
+/// How many clicks leave `dial` at zero
+pub fn part2(input: &str, dial: Dial) -> anyhow::Result<Answer> {
+    visits(input, dial, 0)
+}
+
+/// Reads one rotation per line, so inputs too large to hold in memory can be solved. It takes
+/// the same input as [`parse_rotations`] and fails with the same errors
+pub fn read_rotations(reader: impl BufRead) -> impl Iterator<Item = anyhow::Result<Rotation>> {
+    let parse_line = |line_number: usize, text: &str| {
+        parse_all(line(rotation()), text)
+            .map_err(|mut e: ParseError| {
+                e.line = line_number;
+                e
+            })
+            .context("failed to parse rotations")
+    };
+    // like `parse_rotations`, blank lines and the '\r's ending a line are only allowed at the end
+    // of the input, so the first such line is held back until another line shows they weren't
+    let mut held_back: Option<(usize, String)> = None;
+    let mut lines = reader.lines().enumerate();
+    iter::from_fn(move || {
+        loop {
+            let (i, line) = lines.next()?;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    return Some(Err(e).with_context(|| format!("failed to read line {}", i + 1)));
+                }
+            };
+            let text = line.trim_end_matches('\r');
+            if text.is_empty() {
+                held_back.get_or_insert((i + 1, line));
+                continue;
+            }
+            if let Some((line_number, held_line)) = held_back.take() {
+                return Some(parse_line(line_number, &held_line));
+            }
+            let rotation = parse_line(i + 1, text);
+            if rotation.is_ok() && text.len() < line.len() {
+                held_back = Some((i + 1, line));
+            }
+            return Some(rotation);
+        }
+    })
+}
 
-/// A dial has a maximum value of 0..100
-struct Dial(u8);
+/// Both answers for the rotations so far, updated one rotation at a time
+#[derive(Debug, Clone)]
+pub struct Tally {
+    dial: Dial,
+    target: u64,
+    num_rotations: u64,
+    landings: Answer,
+    visits: Answer,
+}
+
+impl Tally {
+    /// Counts the landings on and visits to `target`, zero for the puzzle
+    pub fn new(dial: Dial, target: u64) -> anyhow::Result<Self> {
+        ensure_on_dial(dial, target)?;
+        Ok(Self {
+            dial,
+            target,
+            num_rotations: 0,
+            landings: Answer::ZERO,
+            visits: Answer::ZERO,
+        })
+    }
 
-impl Dial {
-    const MAX_VALUE: u8 = 100;
-    const fn new(value: u8) -> Option<Self> {
-        if value >= Self::MAX_VALUE {
-            return None;
+    pub fn add(&mut self, rotation: Rotation) {
+        let step = self.dial.step(rotation);
+        self.visits += step.visits(self.target).into();
+        if step.end == self.target {
+            self.landings += Answer::ONE;
         }
-        Some(Self(value))
+        self.num_rotations += 1;
     }
 
-    fn is_zero(&self) -> bool {
-        self.0 == 0
+    /// The dial after the rotations so far
+    pub fn dial(&self) -> Dial {
+        self.dial
     }
 
-    fn add_assign_count_saturations(&mut self, rhs: i16) -> u16 {
-        let (normalized_rhs, mut num_saturations) = if rhs < 0 {
-            let full_rotations: u16 = rhs.unsigned_abs() / Into::<u16>::into(Self::MAX_VALUE);
-            let complement_rhs =
-                (rhs + (Self::MAX_VALUE as u16 * (full_rotations + 1)) as i16) as u16;
-            (complement_rhs, full_rotations)
-        } else {
-            // we know it's above 0, and `i16` always fits into a `u16`
-            (rhs as u16, rhs as u16 / Self::MAX_VALUE as u16)
-        };
+    pub fn num_rotations(&self) -> u64 {
+        self.num_rotations
+    }
 
-        let modulo_rhs = normalized_rhs % Self::MAX_VALUE as u16;
-        let result = ((self.0 as u16 + modulo_rhs) % Self::MAX_VALUE as u16) as u8;
-        if (self.0 != 0 && result != 0)
-            && ((result > self.0 && rhs < 0) || (result < self.0 && rhs > 0))
-        {
-            num_saturations += 1;
-        }
+    /// The part1 answer so far
+    pub fn landings(&self) -> &Answer {
+        &self.landings
+    }
 
-        self.0 = result;
-        num_saturations
+    /// The part2 answer so far
+    pub fn visits(&self) -> &Answer {
+        &self.visits
     }
 }
 
-impl std::ops::AddAssign<i16> for Dial {
-    fn add_assign(&mut self, rhs: i16) {
-        // ignore num rotations for part1
-        self.add_assign_count_saturations(rhs);
+/// Tallies every rotation `reader` has, holding a single line at a time
+pub fn tally(reader: impl BufRead, dial: Dial, target: u64) -> anyhow::Result<Tally> {
+    let mut tally = Tally::new(dial, target)?;
+    for rotation in read_rotations(reader) {
+        tally.add(rotation?);
     }
+    Ok(tally)
 }
 
-pub fn part1(input: &str) -> anyhow::Result<u32> {
-    let mut num_zero = 0;
-    let mut dial = Dial::new(50).expect("50 is a valid dial starting value");
-    for line in input.lines() {
-        let mut chars = line.chars().fuse();
-        let direction = chars.next().context("failed to find direction")?;
-        let distance: i16 = chars
-            .collect::<String>()
-            .parse()
-            .context("failed to parse distance")?;
+/// How many clicks leave `dial` at every one of its positions
+pub fn histogram(input: &str, dial: Dial) -> anyhow::Result<Vec<u128>> {
+    let rotations = parse_rotations(input)?;
+    visit_counts(dial, rotations).with_context(|| {
+        format!(
+            "a dial of size {} is too large for a histogram",
+            dial.size()
+        )
+    })
+}
 
-        let distance = match direction {
-            'L' => -distance,
-            'R' => distance,
-            other => anyhow::bail!("invalid direction: {other}"),
+/// The histogram of `input` as CSV rows
+pub fn write_histogram_csv(input: &str, dial: Dial, out: &mut impl Write) -> anyhow::Result<()> {
+    writeln!(out, "position,visits")?;
+    for (position, visits) in histogram(input, dial)?.iter().enumerate() {
+        writeln!(out, "{position},{visits}")?;
+    }
+    Ok(())
+}
+
+/// Every rotation of `input` as a CSV row, with how often it passed zero without stopping on it
+pub fn write_steps_csv(input: &str, dial: Dial, out: &mut impl Write) -> anyhow::Result<()> {
+    writeln!(out, "start,direction,distance,end,zero_passes")?;
+    for step in dial.steps(parse_rotations(input)?) {
+        let direction = match step.direction() {
+            Direction::Left => 'L',
+            Direction::Right => 'R',
         };
-        dial += distance;
-        if dial.is_zero() {
-            num_zero += 1;
-        }
+        writeln!(
+            out,
+            "{},{direction},{},{},{}",
+            step.start,
+            step.rotation.distance,
+            step.end,
+            step.passes(0)
+        )?;
     }
-    Ok(num_zero)
+    Ok(())
+}
+
+/// `gear <from> <to> <ratio>`
+fn gear<'a>() -> impl Parser<'a, Gear> {
+    tag("gear ")
+        .then(uint())
+        .skip(tag(" "))
+        .then(uint())
+        .skip(tag(" "))
+        .then(int())
+        .map(|(((_, from), to), ratio)| Gear { from, to, ratio })
 }
 
-pub fn part2(input: &str) -> anyhow::Result<u32> {
-    let mut num_rotations = 0;
-    let mut dial = Dial::new(50).expect("50 is a valid dial starting value");
-    for line in input.lines() {
-        let mut chars = line.chars().fuse();
-        let direction = chars.next().context("failed to find direction")?;
-        let distance: i16 = chars
-            .collect::<String>()
-            .parse()
-            .context("failed to parse distance")?;
+/// A rotation of one of the dials of a lock
+pub type Turn = (usize, Rotation);
+
+/// `<dial>:<rotation>`, or a bare rotation of dial 0
+fn turn<'a>() -> impl Parser<'a, Turn> {
+    let numbered = uint().skip(tag(":")).then(rotation());
+    numbered.or(rotation().map(|rotation| (0, rotation)))
+}
+
+/// The gears of a lock, then the turns of its dials
+pub fn parse_lock(input: &str) -> anyhow::Result<(Vec<Gear>, Vec<Turn>)> {
+    let input = input.trim_end_matches(['\n', '\r']);
+    parse_all(many(line(gear())).then(lines(turn())), input).context("failed to parse lock")
+}
 
-        let distance = match direction {
-            'L' => -distance,
-            'R' => distance,
-            other => anyhow::bail!("invalid direction: {other}"),
+/// Locks have a handful of dials, this is only here so a typo in a dial number can't make us
+/// allocate a dial for every number up to it
+const MAX_DIALS: usize = 1024;
+
+/// Turns a lock with as many dials as `input` names, all starting like `dial`
+pub fn open_lock(input: &str, dial: Dial) -> anyhow::Result<Lock> {
+    let (gears, turns) = parse_lock(input)?;
+    let last = gears
+        .iter()
+        .flat_map(|gear| [gear.from, gear.to])
+        .chain(turns.iter().map(|(dial, _)| *dial))
+        .max()
+        .unwrap_or(0);
+    if last >= MAX_DIALS {
+        anyhow::bail!("dial {last} is out of range, a lock has at most {MAX_DIALS} dials");
+    }
+    let num_dials = last + 1;
+    let mut lock = Lock::new(vec![dial; num_dials], gears)?;
+    for (i, (dial, rotation)) in turns.into_iter().enumerate() {
+        lock.turn(dial, rotation).with_context(|| {
+            format!("failed to turn dial {dial} by {rotation} (turn {})", i + 1)
+        })?;
+    }
+    Ok(lock)
+}
+
+/// A line for every dial of the lock in `input`, with where it ended and how often it
+/// stopped on and passed zero
+pub fn write_lock_report(input: &str, dial: Dial, out: &mut impl Write) -> anyhow::Result<()> {
+    let lock = open_lock(input, dial)?;
+    for (i, (dial, counts)) in lock.dials().iter().zip(lock.counts()).enumerate() {
+        writeln!(
+            out,
+            "dial {i}: at {}, {} landings, {} passes",
+            dial.position(),
+            counts.landings,
+            counts.passes
+        )?;
+    }
+    Ok(())
+}
+
+/// Every start of a dial of `size` for which the rotations of `input` reach zero `count` times
+pub fn find_starts(
+    input: &str,
+    size: u64,
+    counted: ZeroCount,
+    count: u64,
+) -> anyhow::Result<IntervalSet> {
+    let rotations = parse_rotations(input)?;
+    starts_with_count(size, &rotations, counted, count)
+        .with_context(|| format!("a dial of size {size} needs at least 2 numbers"))
+}
+
+/// A position on every line
+pub fn parse_targets(input: &str) -> anyhow::Result<Vec<u64>> {
+    let input = input.trim_end_matches(['\n', '\r']);
+    parse_all(lines(uint()), input).context("failed to parse targets")
+}
+
+/// The rotations [`plan`] picks to take `dial` to every target of `input` in order, as a puzzle
+/// input
+pub fn plan_rotations(input: &str, dial: Dial, crossings: Crossings) -> anyhow::Result<String> {
+    let targets = parse_targets(input)?;
+    for &target in &targets {
+        ensure_on_dial(dial, target)?;
+    }
+    let rotations = plan(dial, &targets, crossings).context("a target isn't on the dial")?;
+    Ok(rotations
+        .iter()
+        .map(|rotation| format!("{rotation}\n"))
+        .collect())
+}
+
+/// Which clicks of `step` reached `target`, e.g. `reaches 0 at clicks 50, 150, ..., 950`
+fn describe_clicks(step: &Step, target: u64) -> String {
+    let Some(first) = step.first_click_at(target) else {
+        return if step.end == target {
+            format!("stays on {target}")
+        } else {
+            format!("never reaches {target}")
         };
-        num_rotations += dial.add_assign_count_saturations(distance) as u32;
-        if dial.is_zero() {
-            num_rotations += 1;
+    };
+    let num_clicks = step.visits(target);
+    // the dial comes back to `target` after every full turn
+    let click = |i: u64| first + i * step.dial_size();
+    let clicks = match num_clicks {
+        1 => format!("click {first}"),
+        2..=4 => {
+            let clicks: Vec<String> = (0..num_clicks - 1).map(|i| click(i).to_string()).collect();
+            format!("clicks {} and {}", clicks.join(", "), click(num_clicks - 1))
         }
+        _ => format!(
+            "clicks {first}, {}, ..., {} ({num_clicks} times)",
+            click(1),
+            click(num_clicks - 1)
+        ),
+    };
+    if step.end == target {
+        format!("reaches {target} at {clicks}, stopping there")
+    } else {
+        format!("reaches {target} at {clicks}")
     }
-    Ok(num_rotations)
+}
+
+/// A line for every rotation of `input`, with where it left the dial, the clicks that reached
+/// `target` and what it added to both parts, then the answers
+pub fn explain(input: &str, dial: Dial, target: u64, out: &mut impl Write) -> anyhow::Result<()> {
+    ensure_on_dial(dial, target)?;
+    let (mut landings, mut visits) = (Answer::ZERO, Answer::ZERO);
+    for step in dial.steps(parse_rotations(input)?) {
+        let landed = u64::from(step.end == target);
+        let visited = step.visits(target);
+        writeln!(
+            out,
+            "{}: {} -> {}, {} full rotations, {}; part1 +{landed}, part2 +{visited}",
+            step.rotation,
+            step.start,
+            step.end,
+            step.full_rotations(),
+            describe_clicks(&step, target),
+        )?;
+        landings += landed.into();
+        visits += visited.into();
+    }
+    writeln!(out, "part1: {landings}, part2: {visits}")?;
+    Ok(())
+}
+
+/// Where the dial started, if the rotations of `input` left it at `end`
+pub fn start_position(input: &str, mut end: Dial) -> anyhow::Result<u64> {
+    end.rewind(&parse_rotations(input)?);
+    Ok(end.position())
 }
 
 #[cfg(test)]
 mod tests {
     use indoc::indoc;
 
-    use crate::day1::{Dial, part1, part2};
+    use super::*;
+
+    fn dial() -> Dial {
+        Dial::new(Dial::DEFAULT_START).unwrap()
+    }
+
+    #[test]
+    fn test_invalid_rotations() {
+        assert!(part1("L-32768", dial()).is_err());
+        assert!(part2("R-5", dial()).is_err());
+        assert!(part2("R9223372036854775808", dial()).is_err());
+    }
+
+    #[test]
+    fn test_large_distances() {
+        assert_eq!(part2("R40000", dial()).unwrap(), 400);
+        // 50 clicks to zero, then a full turn every 100
+        assert_eq!(
+            part2("L9223372036854775807", dial()).unwrap(),
+            (i64::MAX as u64 - 50) / 100 + 1
+        );
+    }
+
+    #[test]
+    fn test_dial_size() {
+        let dial = Dial::with_size(10, 5).unwrap();
+        assert_eq!(part1("R5\nL10\nR25\n", dial).unwrap(), 2);
+        assert_eq!(part2("R5\nL10\nR25\n", dial).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_parse_rotations() {
+        let rotations = parse_rotations("L68\nR48\n\n").unwrap();
+        assert_eq!(rotations, ["L68".parse().unwrap(), "R48".parse().unwrap()]);
+        let printed: Vec<String> = rotations.iter().map(Rotation::to_string).collect();
+        assert_eq!(printed, ["L68", "R48"]);
+    }
+
+    #[test]
+    fn test_write_steps_csv() {
+        let mut out = Vec::new();
+        write_steps_csv("L68\nL30\nR48\nR100\n", dial(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            indoc! {"
+                start,direction,distance,end,zero_passes
+                50,L,68,82,1
+                82,L,30,52,0
+                52,R,48,0,0
+                0,R,100,0,0
+            "}
+        );
+    }
+
+    #[test]
+    fn test_target() {
+        let input = "L68\nL30\nR48\nR10\n";
+        assert_eq!(landings(input, dial(), 52).unwrap(), 1);
+        // through 60 going left from 82, and again going right from 52
+        assert_eq!(visits(input, dial(), 60).unwrap(), 2);
+        assert_eq!(
+            visits(input, dial(), 0).unwrap(),
+            part2(input, dial()).unwrap()
+        );
+        assert!(visits(input, dial(), 100).is_err());
+
+        let dial = Dial::with_size(4, 0).unwrap();
+        assert_eq!(histogram("R5\nL2\n", dial).unwrap(), [2, 2, 1, 2]);
+        let mut out = Vec::new();
+        write_histogram_csv("R5\nL2\n", dial, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "position,visits\n0,2\n1,2\n2,1\n3,2\n"
+        );
+    }
+
+    #[test]
+    fn test_lock() {
+        let input = indoc! {"
+            gear 0 1 2
+            gear 1 2 -1
+            R5
+            1:L13
+        "};
+        let mut out = Vec::new();
+        write_lock_report(input, Dial::with_size(10, 0).unwrap(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            indoc! {"
+                dial 0: at 5, 0 landings, 0 passes
+                dial 1: at 7, 1 landings, 1 passes
+                dial 2: at 3, 1 landings, 1 passes
+            "}
+        );
+
+        // a plain day1 input is a lock with a single dial
+        let lock = open_lock("L68\nL30\nR48\n", dial()).unwrap();
+        assert_eq!(lock.counts()[0].landings, 1);
+
+        assert!(open_lock("gear 0 1 1\ngear 1 0 1\nR1\n", dial()).is_err());
+        assert!(open_lock("R1\ngear 0 1 1\n", dial()).is_err());
+        assert!(open_lock("1:X1\n", dial()).is_err());
+        for input in [
+            "18446744073709551615:L5\n",
+            "4000000000:R1\n",
+            "gear 0 1024 1\n",
+        ] {
+            let err = open_lock(input, dial()).unwrap_err();
+            assert!(
+                err.to_string().ends_with("a lock has at most 1024 dials"),
+                "{err}"
+            );
+        }
+        assert_eq!(open_lock("1023:R1\n", dial()).unwrap().dials().len(), 1024);
+    }
+
+    #[test]
+    fn test_tally() {
+        let input = indoc! {"
+            L68
+            L30
+            R48
+            L5
+            R60
+            L55
+            L1
+            L99
+            R14
+            L82
+        "};
+        let tally = tally(input.as_bytes(), dial(), 0).unwrap();
+        assert_eq!(tally.num_rotations(), 10);
+        assert_eq!(*tally.landings(), 3);
+        assert_eq!(*tally.visits(), 6);
+        assert_eq!(tally.dial().position(), 32);
+
+        // the answers so far after every rotation
+        let mut tally = Tally::new(dial(), 0).unwrap();
+        let mut landings = Vec::new();
+        for rotation in read_rotations("L50\r\nR100\r\nR1\r\n".as_bytes()) {
+            tally.add(rotation.unwrap());
+            landings.push(tally.landings().to_string());
+        }
+        assert_eq!(landings, ["1", "2", "2"]);
+
+        let err = super::tally("R1\nX5\n".as_bytes(), dial(), 0).unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "failed to parse rotations: line 2, column 1: expected 'L' or 'R', found 'X'"
+        );
+        assert!(Tally::new(dial(), 100).is_err());
+    }
+
+    #[test]
+    fn test_tally_matches_parts() {
+        let inputs = [
+            "L68\nL30\nR48\n",
+            "L68\nL30\nR48\n\n",
+            "L68\r\nL30\r\nR48\r\n\r\n\r\n",
+            "R1000\nL0\nL50",
+            "",
+            "L68\n\nR48\n",
+            "L68\nR-5\n",
+            "L68 \n",
+            "R9223372036854775808\n",
+            "\r",
+            "L68\r",
+            "L68\n\r\r\n\r",
+            "L68\r\r\nR48\n",
+            "L68\n\r\r\nR48\n",
+            "L2\nL44\nL2\r\r\n\r\r\r\n\r\r",
+            "L2\r\r\n\r\nL44\n",
+        ];
+        for input in inputs {
+            let streamed = tally(input.as_bytes(), dial(), 0);
+            match (streamed, part1(input, dial()), part2(input, dial())) {
+                (Ok(tally), Ok(part1), Ok(part2)) => {
+                    assert_eq!(
+                        (tally.landings(), tally.visits()),
+                        (&part1, &part2),
+                        "{input:?}"
+                    );
+                }
+                (Err(streamed), Err(part1), Err(part2)) => {
+                    assert_eq!(format!("{streamed:#}"), format!("{part1:#}"), "{input:?}");
+                    assert_eq!(format!("{streamed:#}"), format!("{part2:#}"), "{input:?}");
+                }
+                results => panic!("{input:?} gave {results:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_starts() {
+        let input = "L68\nL30\nR48\n";
+        let starts = find_starts(input, 100, ZeroCount::Landings, 1).unwrap();
+        let starts: Vec<String> = starts.iter().map(|range| range.to_string()).collect();
+        assert_eq!(starts, ["50-50", "68-68", "98-98"]);
+
+        let starts = find_starts("R25\n", 10, ZeroCount::Visits, 3).unwrap();
+        let starts: Vec<String> = starts.iter().map(|range| range.to_string()).collect();
+        assert_eq!(starts, ["5-9"]);
+        assert!(find_starts(input, 1, ZeroCount::Visits, 0).is_err());
+    }
+
+    #[test]
+    fn test_plan_rotations() {
+        let targets = "82\n52\n0\n0\n95\n";
+        let plan = plan_rotations(targets, dial(), Crossings::Any).unwrap();
+        assert_eq!(plan, "R32\nL30\nR48\nR0\nL5\n");
+        // the plan is a puzzle input again, landing on zero twice and passing it never
+        assert_eq!(part1(&plan, dial()).unwrap(), 2);
+        assert_eq!(part2(&plan, dial()).unwrap(), 2);
+        assert_eq!(parse_rotations(&plan).unwrap().len(), 5);
+        assert_eq!(
+            plan_rotations(targets, dial(), Crossings::Fewest).unwrap(),
+            plan
+        );
+        // the long way to the first two targets, crossing zero on both
+        let most = plan_rotations(targets, dial(), Crossings::Most).unwrap();
+        assert_eq!(most, "L68\nR70\nR48\nR0\nL5\n");
+        assert_eq!(part1(&most, dial()).unwrap(), 2);
+        assert_eq!(part2(&most, dial()).unwrap(), 4);
+
+        let dial = Dial::with_size(10, 2).unwrap();
+        assert_eq!(plan_rotations("7", dial, Crossings::Most).unwrap(), "L5\n");
+        assert!(plan_rotations("10", dial, Crossings::Any).is_err());
+        assert!(plan_rotations("L5", dial, Crossings::Any).is_err());
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = "L68\nL30\nR48\nR0\nR250\nL1000\nR50\n";
+        let mut out = Vec::new();
+        explain(input, dial(), 0, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            indoc! {"
+                L68: 50 -> 82, 0 full rotations, reaches 0 at click 50; part1 +0, part2 +1
+                L30: 82 -> 52, 0 full rotations, never reaches 0; part1 +0, part2 +0
+                R48: 52 -> 0, 0 full rotations, reaches 0 at click 48, stopping there; part1 +1, part2 +1
+                R0: 0 -> 0, 0 full rotations, stays on 0; part1 +1, part2 +1
+                R250: 0 -> 50, 2 full rotations, reaches 0 at clicks 100 and 200; part1 +0, part2 +2
+                L1000: 50 -> 50, 10 full rotations, reaches 0 at clicks 50, 150, ..., 950 (10 times); part1 +0, part2 +10
+                R50: 50 -> 0, 0 full rotations, reaches 0 at click 50, stopping there; part1 +1, part2 +1
+                part1: 3, part2: 16
+            "}
+        );
+        assert_eq!(part1(input, dial()).unwrap(), 3);
+        assert_eq!(part2(input, dial()).unwrap(), 16);
+        assert!(explain(input, dial(), 100, &mut Vec::new()).is_err());
+    }
 
     #[test]
-    fn test_dial() {
-        let mut dial = Dial::new(11).unwrap();
-        dial += 8;
-        assert_eq!(dial.0, 19);
-        dial += -19;
-        assert_eq!(dial.0, 0);
-
-        let mut dial = Dial::new(0).unwrap();
-        dial += -1;
-        assert_eq!(dial.0, 99);
-        dial += 1;
-        assert_eq!(dial.0, 0);
-
-        let mut dial = Dial::new(5).unwrap();
-        dial += -10;
-        assert_eq!(dial.0, 95);
-        dial += 5;
-        assert_eq!(dial.0, 0);
+    fn test_start_position() {
+        let input = "L68\nL30\nR48\n";
+        assert_eq!(start_position(input, Dial::new(0).unwrap()).unwrap(), 50);
+        let dial = Dial::with_size(10, 3).unwrap();
+        assert_eq!(start_position("R25\nL2\n", dial).unwrap(), 0);
     }
 
     #[test]
@@ -136,7 +655,7 @@ mod tests {
             R14
             L82"
         };
-        let result = part1(input).unwrap();
+        let result = part1(input, dial()).unwrap();
         assert_eq!(result, 3);
     }
 
@@ -154,7 +673,7 @@ mod tests {
             R14
             L82"
         };
-        let result = part2(input).unwrap();
+        let result = part2(input, dial()).unwrap();
         assert_eq!(result, 6);
     }
 }
@@ -1,128 +1,81 @@
-use std::{ops::RangeInclusive, str::FromStr};
+use std::str::FromStr;
 
 use anyhow::Context;
+use aoc_core::{
+    answer::Answer,
+    day2::{is_valid_id, is_valid_id_part2},
+    range::InclusiveRange,
+    stats::{self, Counter},
+};
 
-/// An invalid ID is a number which is made only of some sequence of digits repeated twice
-fn is_valid_id(num: u64) -> bool {
-    let num_digits = num.ilog10() + 1;
-    if num_digits % 2 != 0 {
-        // odd number of digits, so we can't have a repeating sequence twice
-        return true;
-    }
-
-    let pow = 10u64.pow(num_digits / 2);
-    let higher_digits = num / pow;
-    let lower_digits = num % pow;
-    higher_digits != lower_digits
-}
+use crate::parse::{ParseError, Parser, parse_all, range, separated, tag};
 
-/// Now, an ID is invalid if it is made only of some sequence of digits repeated at least twice
-fn is_valid_id_part2(num: u64) -> bool {
-    // eprintln!("num: {num}");
-    let num_digits = num.ilog10() + 1;
-
-    if num < 10 {
-        // a single digit isn't repeated at least twice
-        return true;
-    }
-
-    for digits_to_check in 1..num_digits {
-        if num_digits % digits_to_check != 0 {
-            // if we can't split to repeated sequences it can't be an invalid ID
-            continue;
-        }
-        // eprintln!("digits_to_check: {digits_to_check}");
-
-        let modulo = 10u64.pow(digits_to_check);
-        let sequence = num % modulo;
-        // eprintln!("sequence: {sequence}");
-        let mut found_different = false;
-        let mut i = 1;
-        loop {
-            let pow = 10u64.pow(digits_to_check * i);
-            if pow > num {
-                break;
-            }
-            // eprintln!("pow: {pow}, checking: {}", (num / pow) % modulo);
-            if (num / pow) % modulo != sequence {
-                found_different = true;
-                break;
-            }
-            i += 1;
-        }
-
-        if !found_different {
-            return false;
-        }
-    }
-
-    true
-}
-
-struct IdRange(RangeInclusive<u64>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct IdRange(InclusiveRange);
 
 impl IdRange {
-    pub fn new(range: RangeInclusive<u64>) -> Self {
+    pub fn new(range: InclusiveRange) -> Self {
         Self(range)
     }
 
-    pub fn sum_invalid_ids(self) -> u64 {
-        let mut sum_invalid = 0;
-        for num in self.0 {
-            if !is_valid_id(num) {
-                sum_invalid += Into::<u64>::into(num);
-            }
-        }
-
-        sum_invalid
+    /// The number of IDs in the range
+    // a range always holds at least its start, so there is no `is_empty`
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u128 {
+        self.0.len()
     }
 
-    pub fn sum_invalid_ids_part2(self) -> u64 {
-        let mut sum_invalid = 0;
-        for num in self.0 {
-            if !is_valid_id_part2(num) {
-                sum_invalid += Into::<u64>::into(num);
-            }
-        }
+    pub fn sum_invalid_ids(&self) -> Answer {
+        stats::add(
+            Counter::NumbersChecked,
+            self.len().try_into().unwrap_or(u64::MAX),
+        );
+        self.0
+            .iter()
+            .filter(|num| !is_valid_id(*num))
+            .map(Answer::from)
+            .sum()
+    }
 
-        sum_invalid
+    pub fn sum_invalid_ids_part2(&self) -> Answer {
+        stats::add(
+            Counter::NumbersChecked,
+            self.len().try_into().unwrap_or(u64::MAX),
+        );
+        self.0
+            .iter()
+            .filter(|num| !is_valid_id_part2(*num))
+            .map(Answer::from)
+            .sum()
     }
 }
 
 impl FromStr for IdRange {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // <num>-<num>
-        let mut split = s.split('-').fuse();
-        let start = split
-            .next()
-            .context("no start number")?
-            .parse()
-            .with_context(|| format!("failed to parse start from: {s}"))?;
-        let end = split
-            .next()
-            .context("no end number")?
-            .parse()
-            .with_context(|| format!("failed to parse end: {s}"))?;
-        Ok(Self::new(start..=end))
+        parse_all(range(), s).map(Self::new)
     }
 }
 
-pub fn part1(input: &str) -> anyhow::Result<u64> {
-    let mut sum_invalid = 0;
-    for possible_range in input.split(',') {
-        let range: IdRange = possible_range.parse().context("failed to parse range")?;
+pub fn parse_ranges(input: &str) -> anyhow::Result<Vec<IdRange>> {
+    let ranges = separated(range().map(IdRange::new), tag(","));
+    parse_all(ranges, input).context("failed to parse ranges")
+}
+
+pub fn part1(input: &str) -> anyhow::Result<Answer> {
+    let mut sum_invalid = Answer::ZERO;
+    for range in parse_ranges(input)? {
         sum_invalid += range.sum_invalid_ids();
     }
 
     Ok(sum_invalid)
 }
 
-pub fn part2(input: &str) -> anyhow::Result<u64> {
-    let mut sum_invalid = 0;
-    for possible_range in input.split(',') {
-        let range: IdRange = possible_range.parse().context("failed to parse range")?;
+pub fn part2(input: &str) -> anyhow::Result<Answer> {
+    let mut sum_invalid = Answer::ZERO;
+    for range in parse_ranges(input)? {
         sum_invalid += range.sum_invalid_ids_part2();
     }
 
@@ -146,13 +99,6 @@ mod tests {
         assert_eq!(result, 1227775554);
     }
 
-    #[test]
-    fn test_is_valid_id_part2() {
-        assert!(!is_valid_id_part2(2121212121));
-        assert!(is_valid_id_part2(2121212120));
-        assert!(is_valid_id_part2(3));
-    }
-
     #[test]
     fn test_part2() {
         let input = indoc! {"
@@ -1,93 +1,57 @@
-use std::str::FromStr;
+use std::{ops::Deref, str::FromStr};
 
 use anyhow::Context;
+use aoc_core::answer::Answer;
 
-struct PowerBank {
-    digits: Vec<u8>,
-}
+use crate::parse::{ParseError, Parser, char_map, lines, many, parse_all};
 
-impl PowerBank {
-    fn find_max_from_index(&self, from: usize, to: usize) -> anyhow::Result<(usize, u8)> {
-        // we don't use `.max()` since it returns the last one if multiple are equal
-        let mut max_num_and_index = None;
-        for (i, d) in self.digits[from..to].iter().enumerate() {
-            if let Some((_, max_d)) = &max_num_and_index {
-                if d > max_d {
-                    max_num_and_index = Some((i, *d));
-                }
-            } else {
-                max_num_and_index = Some((i, *d));
-            }
-        }
-
-        let Some(max_num_and_index) = max_num_and_index else {
-            anyhow::bail!("digits are empty");
-        };
-
-        Ok(max_num_and_index)
-    }
+/// Parses a bank of battery joltages, one digit per battery
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct PowerBank(aoc_core::day3::PowerBank);
 
-    fn sum_top_2(&self) -> anyhow::Result<u16> {
-        // we always want to first find the highest number that appears first,
-        // since no matter what it'll be higher than even if we find a 9
-        // that is after it
-        let (i, tens) = self
-            .find_max_from_index(0, self.digits.len() - 1)
-            .context("couldn't find top 1")?;
-        let (_, ones) = self
-            .find_max_from_index(i + 1, self.digits.len())
-            .context("couldn't find second")?;
-        Ok(tens as u16 * 10 + ones as u16)
-    }
+impl Deref for PowerBank {
+    type Target = aoc_core::day3::PowerBank;
 
-    fn sum_top_12(&self) -> anyhow::Result<u64> {
-        let mut sum = 0;
-        let mut from = 0;
-        for i in 0..12 {
-            let (next_from, value) = self
-                .find_max_from_index(from, self.digits.len() - 11 + i)
-                .with_context(|| format!("failed to find max for {i}"))?;
-            from = from + next_from + 1;
-            sum += value as u64 * 10u64.pow((11 - i) as u32);
-        }
-
-        Ok(sum)
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
+fn power_bank<'a>() -> impl Parser<'a, PowerBank> {
+    let digit = char_map("a digit", |c| {
+        c.to_digit(10).and_then(|d| u8::try_from(d).ok())
+    });
+    many(digit).map(|digits| PowerBank(aoc_core::day3::PowerBank::new(digits)))
+}
+
 impl FromStr for PowerBank {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut digits = Vec::with_capacity(s.len());
-        for c in s.chars().into_iter() {
-            let d = c.to_digit(10).context("failed to convert char to digit")?;
-            if d >= u8::MAX as u32 {
-                anyhow::bail!("digit {d} is above the max value");
-            }
-            let d = d as u8;
-            digits.push(d);
-        }
-
-        Ok(Self { digits })
+        parse_all(power_bank(), s)
     }
 }
 
-pub fn part1(input: &str) -> anyhow::Result<u32> {
-    let mut sum = 0;
-    for line in input.lines() {
-        let power_bank: PowerBank = line.parse().context("failed to parse line")?;
-        sum += power_bank.sum_top_2().context("failed to sum top 2")? as u32;
+pub fn parse_power_banks(input: &str) -> anyhow::Result<Vec<PowerBank>> {
+    parse_all(lines(power_bank()), input).context("failed to parse power banks")
+}
+
+pub fn part1(input: &str) -> anyhow::Result<Answer> {
+    let mut sum = Answer::ZERO;
+    for power_bank in parse_power_banks(input)? {
+        let top_2 = power_bank.sum_top_2().context("failed to sum top 2")?;
+        sum += top_2.into();
     }
 
     Ok(sum)
 }
 
-pub fn part2(input: &str) -> anyhow::Result<u64> {
-    let mut sum = 0;
-    for line in input.lines() {
-        let power_bank: PowerBank = line.parse().context("failed to parse line")?;
-        sum += power_bank.sum_top_12().context("failed to sum top 12")? as u64;
+pub fn part2(input: &str) -> anyhow::Result<Answer> {
+    let mut sum = Answer::ZERO;
+    for power_bank in parse_power_banks(input)? {
+        let top_12 = power_bank.sum_top_12().context("failed to sum top 12")?;
+        sum += top_12.into();
     }
 
     Ok(sum)
@@ -118,4 +82,12 @@ mod tests {
             818181911112111"};
         assert_eq!(part2(input).unwrap(), 3121910778619);
     }
+
+    #[test]
+    fn test_short_banks_are_errors() {
+        assert!(part1("").is_ok());
+        assert!(part1("\n").is_err());
+        assert!(part1("9").is_err());
+        assert!(part2("98765432111").is_err());
+    }
 }
@@ -1,151 +1,91 @@
-use std::{ops::RangeInclusive, str::FromStr};
+use std::str::FromStr;
 
 use anyhow::Context;
+use aoc_core::{answer::Answer, interval_set::IntervalSet, range::InclusiveRange};
 
-pub struct IngredientIdRange(RangeInclusive<u64>);
-
-impl FromStr for IngredientIdRange {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split('-').fuse();
-        let start = split
-            .next()
-            .context("no start")?
-            .parse()
-            .context("failed to parse start")?;
-        let end = split
-            .next()
-            .context("no end")?
-            .parse()
-            .context("failed to parse end")?;
-        Ok(Self(start..=end))
-    }
-}
+use crate::parse::{ParseError, Parser, lines, parse_all, range, section, uint};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "IngredientLists"))]
 pub struct IngredientDatabase {
-    ingredient_id_ranges: Vec<IngredientIdRange>,
+    ingredient_id_ranges: Vec<InclusiveRange>,
     ingredients: Vec<u64>,
+    // private along with what it's built from, so they can't get out of sync
+    #[cfg_attr(feature = "serde", serde(skip))]
+    fresh: IntervalSet,
 }
 
-impl IngredientDatabase {
-    fn is_fresh(&self, id: &u64) -> bool {
-        for range in &self.ingredient_id_ranges {
-            if range.0.contains(id) {
-                return true;
-            }
-        }
+/// What a database deserializes from, `fresh` is rebuilt from the ranges
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct IngredientLists {
+    ingredient_id_ranges: Vec<InclusiveRange>,
+    ingredients: Vec<u64>,
+}
 
-        false
+#[cfg(feature = "serde")]
+impl From<IngredientLists> for IngredientDatabase {
+    fn from(lists: IngredientLists) -> Self {
+        Self::new(lists.ingredient_id_ranges, lists.ingredients)
     }
 }
 
-impl FromStr for IngredientDatabase {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut ingredient_id_ranges: Vec<IngredientIdRange> = vec![];
-        let mut ingredients: Vec<u64> = vec![];
-        let mut found_blank = false;
-        for line in s.lines() {
-            if line.is_empty() {
-                found_blank = true;
-            } else if !found_blank {
-                ingredient_id_ranges.push(line.parse().context("failed to parse range")?);
-            } else {
-                ingredients.push(line.parse().context("failed to parse id")?);
-            }
-        }
-
-        Ok(Self {
+impl IngredientDatabase {
+    pub fn new(ingredient_id_ranges: Vec<InclusiveRange>, ingredients: Vec<u64>) -> Self {
+        Self {
+            fresh: ingredient_id_ranges.iter().copied().collect(),
             ingredient_id_ranges,
             ingredients,
-        })
+        }
     }
-}
-
-pub fn part1(input: &str) -> anyhow::Result<u32> {
-    let database: IngredientDatabase = input.parse().context("failed to parse database")?;
 
-    let mut count = 0;
-    for ingredient in &database.ingredients {
-        if database.is_fresh(ingredient) {
-            count += 1;
-        }
+    /// The fresh ID ranges as they were given
+    pub fn ingredient_id_ranges(&self) -> &[InclusiveRange] {
+        &self.ingredient_id_ranges
     }
 
-    Ok(count)
-}
+    /// The available ingredient IDs
+    pub fn ingredients(&self) -> &[u64] {
+        &self.ingredients
+    }
 
-#[derive(Debug, Clone, Copy)]
-struct MyRangeInclusive {
-    start: u64,
-    end: u64,
-}
+    pub fn is_fresh(&self, id: &u64) -> bool {
+        self.fresh.contains(*id)
+    }
 
-impl MyRangeInclusive {
-    fn count(&self) -> u64 {
-        self.end - self.start + 1
+    /// The ID ranges with every overlapping or touching pair merged, sorted by their start
+    pub fn merged_ranges(&self) -> Vec<InclusiveRange> {
+        self.fresh.iter().collect()
     }
 }
 
-fn do_ranges_intersect(r1: &MyRangeInclusive, r2: &MyRangeInclusive) -> bool {
-    !(r1.end < r2.start || r2.end < r1.start)
-}
+impl FromStr for IngredientDatabase {
+    type Err = ParseError;
 
-fn combine_intersecting_ranges_single_iteration(
-    ranges: Vec<MyRangeInclusive>,
-) -> Vec<MyRangeInclusive> {
-    let Some(mut current) = ranges.get(0).cloned() else {
-        return vec![];
-    };
-    let mut new_ranges = Vec::new();
-
-    for range in ranges.iter().skip(1) {
-        if do_ranges_intersect(&current, range) {
-            let new_min = std::cmp::min(current.start, range.start);
-            let new_max = std::cmp::max(current.end, range.end);
-            current = MyRangeInclusive {
-                start: new_min,
-                end: new_max,
-            };
-        } else {
-            new_ranges.push(current);
-            current = range.clone();
-        }
+    /// The fresh ID ranges, a blank line, and the available ingredient IDs
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let database = section(lines(range())).then(lines(uint()));
+        let (ingredient_id_ranges, ingredients) = parse_all(database, s)?;
+        Ok(Self::new(ingredient_id_ranges, ingredients))
     }
-    new_ranges.push(current);
-
-    new_ranges
 }
 
-fn combine_intersecting_ranges(mut ranges: Vec<MyRangeInclusive>) -> Vec<MyRangeInclusive> {
-    ranges.sort_by_key(|x| (x.start, x.end));
+pub fn part1(input: &str) -> anyhow::Result<Answer> {
+    let database: IngredientDatabase = input.parse().context("failed to parse database")?;
 
-    let mut current_len = ranges.len();
-    loop {
-        ranges = combine_intersecting_ranges_single_iteration(ranges);
-        eprintln!("{:?}", ranges);
-        if ranges.len() == current_len {
-            return ranges;
+    let mut count: usize = 0;
+    for ingredient in database.ingredients() {
+        if database.is_fresh(ingredient) {
+            count += 1;
         }
-        current_len = ranges.len();
     }
+
+    Ok(count.into())
 }
 
-pub fn part2(input: &str) -> anyhow::Result<u64> {
+pub fn part2(input: &str) -> anyhow::Result<Answer> {
     let database: IngredientDatabase = input.parse().context("failed to parse database")?;
-    let ranges: Vec<_> = database
-        .ingredient_id_ranges
-        .into_iter()
-        .map(|x| MyRangeInclusive {
-            start: *x.0.start(),
-            end: *x.0.end(),
-        })
-        .collect();
-
-    let combined = combine_intersecting_ranges(ranges);
-    Ok(combined.into_iter().map(|x| x.count()).sum())
+    Ok(database.fresh.len().into())
 }
 
 #[cfg(test)]
@@ -190,4 +130,33 @@ mod tests {
         let result = part2(input).unwrap();
         assert_eq!(result, 14);
     }
+
+    #[test]
+    fn test_invalid_ranges() {
+        let err = part1("5-3\n\n4\n").unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "failed to parse database: line 1, column 1: range start 5 is after its end 3"
+        );
+        assert_eq!(
+            part2("0-18446744073709551615\n").unwrap().to_string(),
+            "18446744073709551616"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let database: IngredientDatabase = "3-5\n10-14\n12-18\n\n5\n8\n".parse().unwrap();
+        let json = serde_json::to_string(&database).unwrap();
+        assert_eq!(
+            json,
+            r#"{"ingredient_id_ranges":[{"start":3,"end":5},{"start":10,"end":14},{"start":12,"end":18}],"ingredients":[5,8]}"#
+        );
+
+        let reloaded: IngredientDatabase = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.merged_ranges(), database.merged_ranges());
+        assert!(reloaded.is_fresh(&13));
+        assert!(!reloaded.is_fresh(&8));
+    }
 }
@@ -1,172 +1,178 @@
-use std::collections::BTreeMap;
-
 use anyhow::Context;
+use aoc_core::answer::Answer;
+
+use crate::parse::{Parser, char_map, fixed_width, lines, parse_all, uint, word, words};
 
 #[derive(Debug)]
-enum Operation {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Operation {
     Add,
     Mul,
 }
 
 #[derive(Debug)]
-struct Problem {
-    numbers: Vec<u16>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Problem {
+    numbers: Vec<u64>,
     operation: Operation,
 }
 
-fn parse_problems(input: &str) -> anyhow::Result<Vec<Problem>> {
-    let mut problem_builders: BTreeMap<usize, Vec<u16>> = BTreeMap::new();
+/// A line of the worksheet
+enum Row {
+    Numbers(Vec<u64>),
+    Operations(Vec<Operation>),
+}
+
+fn operation<'a>() -> impl Parser<'a, Operation> {
+    char_map("'+' or '*'", |c| match c {
+        '+' => Some(Operation::Add),
+        '*' => Some(Operation::Mul),
+        _ => None,
+    })
+}
+
+/// Puts every number under the operation of its column
+fn collect_problems(rows: Vec<Row>) -> Vec<Problem> {
+    let mut columns: Vec<Vec<u64>> = Vec::new();
     let mut problems = Vec::new();
-    for line in input.lines() {
-        if line.starts_with("*") || line.starts_with("+") {
-            problems = parse_operation_line(&problem_builders, line)
-                .context("failed to parse operation line")?;
-        } else {
-            for (i, number_str) in line.split_whitespace().into_iter().enumerate() {
-                let number: u16 = number_str.parse().context("failed to parse number")?;
-                problem_builders
-                    .entry(i)
-                    .and_modify(|x: &mut Vec<u16>| x.push(number))
-                    .or_insert_with(|| vec![number]);
+    for row in rows {
+        match row {
+            Row::Numbers(numbers) => {
+                for (column, number) in numbers.into_iter().enumerate() {
+                    match columns.get_mut(column) {
+                        Some(numbers) => numbers.push(number),
+                        None => columns.push(vec![number]),
+                    }
+                }
+            }
+            Row::Operations(operations) => {
+                problems = operations
+                    .into_iter()
+                    .zip(&columns)
+                    .map(|(operation, numbers)| Problem {
+                        numbers: numbers.clone(),
+                        operation,
+                    })
+                    .collect();
             }
         }
     }
 
-    Ok(problems)
-}
-
-fn get_problems_grand_total(problems: &[Problem]) -> u64 {
     problems
-        .iter()
-        .map(|x| match x.operation {
-            Operation::Add => x.numbers.iter().copied().map(|x| x as u64).sum::<u64>(),
-            Operation::Mul => x.numbers.iter().copied().map(|x| x as u64).product::<u64>(),
-        })
-        .sum()
 }
 
-pub fn part1(input: &str) -> anyhow::Result<u64> {
-    let problems = parse_problems(input).context("failed to parse problems")?;
-    Ok(get_problems_grand_total(&problems))
+pub fn parse_problems(input: &str) -> anyhow::Result<Vec<Problem>> {
+    let row = words(uint())
+        .map(Row::Numbers)
+        .or(words(operation()).map(Row::Operations));
+    let rows = parse_all(lines(row), input)?;
+    Ok(collect_problems(rows))
 }
 
-fn find_max_digits_for_column(input: &str) -> Vec<usize> {
-    let mut max_digits_per_columns: BTreeMap<usize, usize> = BTreeMap::new();
-    for line in input.lines() {
-        for (column, num) in line.split_whitespace().enumerate() {
-            max_digits_per_columns
-                .entry(column)
-                .and_modify(|len| {
-                    if num.len() > *len {
-                        *len = num.len()
-                    }
-                })
-                .or_insert(num.len());
-        }
+fn get_problems_grand_total(problems: &[Problem]) -> Answer {
+    let mut grand_total = Answer::ZERO;
+    for problem in problems {
+        let numbers = problem.numbers.iter().copied().map(Answer::from);
+        grand_total += match problem.operation {
+            Operation::Add => numbers.sum(),
+            Operation::Mul => numbers.product(),
+        };
     }
 
-    max_digits_per_columns.into_values().collect()
+    grand_total
 }
 
-fn parse_problems_part2(input: &str) -> anyhow::Result<Vec<Problem>> {
-    let mut problem_builders: BTreeMap<usize, Vec<u16>> = BTreeMap::new();
-    let mut problems = Vec::new();
+pub fn part1(input: &str) -> anyhow::Result<Answer> {
+    let problems = parse_problems(input).context("failed to parse problems")?;
+    Ok(get_problems_grand_total(&problems))
+}
 
-    let max_digits_per_column = find_max_digits_for_column(input);
-
-    for line in input.lines() {
-        if line.starts_with("*") || line.starts_with("+") {
-            problems = parse_operation_line(&problem_builders, line)
-                .context("failed to parse operation line")?;
-        } else {
-            let mut consumed_so_far = 0;
-            for (column, chars_to_take) in max_digits_per_column.iter().enumerate() {
-                let number = if consumed_so_far + chars_to_take > line.len() {
-                    // the last number might not have enough digits, and we'll need to pad it
-                    let num = &line[consumed_so_far..].trim_start();
-                    let num_missing = chars_to_take - num.len();
-                    let num: u16 = num
-                        .parse()
-                        .with_context(|| format!("failed to parse number: {num}"))?;
-                    num * 10u16.pow(num_missing as u32)
-                } else {
-                    let num = &line[consumed_so_far..consumed_so_far + chars_to_take];
-                    let count_zeros_to_add =
-                        num.chars().rev().take_while(|c| c.is_whitespace()).count();
-                    let num = num.trim();
-                    // +1 for the whitespace
-                    consumed_so_far += chars_to_take + 1;
-                    let num: u16 = num
-                        .parse()
-                        .with_context(|| format!("failed to parse number: {num}"))?;
-                    num * 10u16.pow(count_zeros_to_add as u32)
-                };
-
-                problem_builders
-                    .entry(column)
-                    .and_modify(|x: &mut Vec<u16>| x.push(number))
-                    .or_insert_with(|| vec![number]);
+fn find_max_digits_for_column(input: &str) -> anyhow::Result<Vec<usize>> {
+    let mut max_digits_per_column: Vec<usize> = Vec::new();
+    for row in parse_all(lines(words(word())), input)? {
+        for (column, num) in row.iter().enumerate() {
+            match max_digits_per_column.get_mut(column) {
+                Some(len) => *len = (*len).max(num.len()),
+                None => max_digits_per_column.push(num.len()),
             }
         }
     }
 
-    Ok(problems)
+    Ok(max_digits_per_column)
 }
 
-fn parse_operation_line(
-    problem_builders: &BTreeMap<usize, Vec<u16>>,
-    line: &str,
-) -> anyhow::Result<Vec<Problem>> {
-    let mut problems = Vec::new();
-    for (op, numbers) in line
-        .split_whitespace()
-        .into_iter()
-        .zip(problem_builders.values())
-    {
-        let operation = match op {
-            "+" => Operation::Add,
-            "*" => Operation::Mul,
-            other => anyhow::bail!("{other} is not an op"),
-        };
-
-        problems.push(Problem {
-            numbers: numbers.clone(),
-            operation,
-        });
-    }
+/// Reads a cell keeping its alignment, a missing digit on the right counting as a zero
+fn parse_padded_number(cell: &str, width: usize) -> Result<u64, String> {
+    let (num, count_zeros_to_add) = if cell.len() < width {
+        // the last number might not have enough digits, and we'll need to pad it
+        let num = cell.trim_start();
+        (num, width - num.len())
+    } else {
+        let count_zeros_to_add = cell.chars().rev().take_while(|c| c.is_whitespace()).count();
+        (cell.trim(), count_zeros_to_add)
+    };
+    let number: u64 = num
+        .parse()
+        .map_err(|_| format!("failed to parse number: {num}"))?;
+    u32::try_from(count_zeros_to_add)
+        .ok()
+        .and_then(|zeros| 10u64.checked_pow(zeros))
+        .and_then(|pow| number.checked_mul(pow))
+        .ok_or_else(|| format!("padding {num} overflowed"))
+}
 
-    Ok(problems)
+pub fn parse_problems_part2(input: &str) -> anyhow::Result<Vec<Problem>> {
+    let max_digits_per_column = find_max_digits_for_column(input)?;
+
+    let numbers = fixed_width(&max_digits_per_column, 1).try_map(|cells| {
+        cells
+            .into_iter()
+            .zip(&max_digits_per_column)
+            .map(|(cell, width)| parse_padded_number(cell, *width))
+            .collect::<Result<Vec<_>, _>>()
+    });
+    let row = numbers
+        .map(Row::Numbers)
+        .or(words(operation()).map(Row::Operations));
+    let rows = parse_all(lines(row), input)?;
+    Ok(collect_problems(rows))
 }
 
-pub fn part2(input: &str) -> anyhow::Result<u64> {
+pub fn part2(input: &str) -> anyhow::Result<Answer> {
     let problems = parse_problems_part2(input).context("failed to parse problems")?;
     // now we have all the numbers aligned. We should get the grand total by going through the column
 
-    let mut total_sum = 0;
+    let mut total_sum = Answer::ZERO;
     for problem in problems {
-        let Some(max_digits) = problem.numbers.iter().map(|x| x.ilog10() + 1).max() else {
+        let Some(max_digits) = problem
+            .numbers
+            .iter()
+            .map(|x| x.checked_ilog10().unwrap_or(0) + 1)
+            .max()
+        else {
             anyhow::bail!("numbers are empty");
         };
 
         let mut total = match &problem.operation {
-            Operation::Add => 0,
-            Operation::Mul => 1,
+            Operation::Add => Answer::ZERO,
+            Operation::Mul => Answer::ONE,
         };
 
         for pow in 0..max_digits {
-            let mut final_number = 0;
-            let mut times = 0;
-            for num in problem.numbers.iter().rev() {
-                let pow = pow as u32;
-                let to_add = ((num / 10u16.pow(pow)) % 10) * 10u16.pow(times);
-                if to_add > 0 {
-                    times += 1;
+            // the column has a digit from every number that is long enough, the top one first
+            let mut final_number = Answer::ZERO;
+            for num in &problem.numbers {
+                // `max_digits` is at most 20, so this never overflows a `u64`
+                let digit = (num / 10u64.pow(pow)) % 10;
+                if digit == 0 {
+                    continue;
                 }
-                final_number += to_add;
+                final_number = final_number * Answer::from(10u8) + Answer::from(digit);
             }
             match &problem.operation {
-                Operation::Add => total += final_number as u64,
-                Operation::Mul => total *= final_number as u64,
+                Operation::Add => total += final_number,
+                Operation::Mul => total *= final_number,
             }
         }
         total_sum += total;
@@ -203,4 +209,22 @@ mod tests {
         let result = part2(input).unwrap();
         assert_eq!(result, 3263827);
     }
+
+    #[test]
+    fn test_malformed_inputs() {
+        // a zero has no digits to take
+        assert_eq!(part2("0\n+\n").unwrap(), 0);
+        // columns that end inside a multi-byte char
+        assert!(part2("é 1\n12 1\n+ +\n").is_err());
+        // products past a `u128` become big integers
+        let max = "18446744073709551615\n";
+        assert_eq!(
+            part1(&format!("{max}{max}{max}*")).unwrap().to_string(),
+            "6277101735386680762814942322444851025767571854389858533375"
+        );
+        // 2 padded to 20 digits doesn't fit into a `u64`
+        let input = format!("2{}\n10000000000000000000\n+", " ".repeat(19));
+        let err = part2(&input).unwrap_err();
+        assert!(format!("{err:#}").contains("padding 2 overflowed"));
+    }
 }
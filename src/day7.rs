@@ -1,198 +1,54 @@
-use std::{
-    collections::{HashMap, HashSet},
-    mem,
-    str::FromStr,
-};
+use std::{ops::Deref, str::FromStr};
 
 use anyhow::Context;
+use aoc_core::{
+    answer::Answer,
+    day7::{Location, ManifoldWalker, QuantumManifoldWalker},
+};
 
-#[derive(Debug, Clone, Copy)]
-enum Location {
-    Empty,
-    Splitter,
-}
+use crate::parse::{ParseError, char_grid_with_marker, parse_all};
 
-#[derive(Debug)]
-struct Manifold {
-    grid: Vec<Vec<Location>>,
-    start: (usize, usize),
-}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Manifold(aoc_core::day7::Manifold);
+
+impl Deref for Manifold {
+    type Target = aoc_core::day7::Manifold;
 
-impl Manifold {
-    fn get(&self, row_column: (usize, usize)) -> Option<Location> {
-        self.grid
-            .get(row_column.0)
-            .and_then(|row| row.get(row_column.1))
-            .copied()
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
 impl FromStr for Manifold {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
+    /// The beam source `S` is an empty location
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut start = None;
-        let mut grid = Vec::new();
-        for (row, line) in s.lines().enumerate() {
-            let mut grid_row = Vec::with_capacity(line.len());
-            for (column, c) in line.char_indices() {
-                let location = match c {
-                    '.' => Location::Empty,
-                    '^' => Location::Splitter,
-                    'S' => {
-                        start = Some((row, column));
-                        Location::Empty
-                    }
-                    other => anyhow::bail!("{other} is not a valid location"),
-                };
-                grid_row.push(location);
-            }
-            grid.push(grid_row);
-        }
-
-        Ok(Self {
-            grid,
-            start: start.context("no start found")?,
-        })
+        let manifold = char_grid_with_marker('S', |_, c| match c {
+            '.' | 'S' => Some(Location::Empty),
+            '^' => Some(Location::Splitter),
+            _ => None,
+        });
+        let (grid, start) = parse_all(manifold, s)?;
+        Ok(Self(aoc_core::day7::Manifold::new(grid, start)))
     }
 }
 
-#[derive(Debug)]
-struct ManifoldWalker<'a> {
-    manifold: &'a Manifold,
-    beams: Vec<(usize, usize)>,
-}
-
-impl<'a> ManifoldWalker<'a> {
-    fn new(manifold: &'a Manifold) -> Self {
-        Self {
-            manifold,
-            beams: vec![manifold.start],
-        }
-    }
-
-    fn step(&mut self) -> Option<usize> {
-        let mut splits = 0;
-        let mut new_beams = HashSet::new();
-        let current_beams = mem::take(&mut self.beams);
-        for beam in current_beams {
-            let (next_row, next_column) = (beam.0 + 1, beam.1);
-
-            // we assume the final row can't have a splitter at the edge
-            if next_row == self.manifold.grid.len() {
-                return None;
-            }
-            let next = (next_row, next_column);
-
-            let Some(location) = self.manifold.get(next) else {
-                continue;
-            };
-
-            match location {
-                Location::Empty => {
-                    new_beams.insert(next);
-                }
-                Location::Splitter => {
-                    splits += 1;
-                    new_beams.insert((next_row, next_column + 1));
-                    new_beams.insert((next_row, next_column - 1));
-                }
-            };
-        }
-        self.beams = new_beams.into_iter().collect();
-
-        Some(splits)
-    }
-}
-
-pub fn part1(input: &str) -> anyhow::Result<usize> {
+pub fn part1(input: &str) -> anyhow::Result<Answer> {
     let manifold: Manifold = input.parse().context("failed to parse input")?;
     let mut walker = ManifoldWalker::new(&manifold);
-    let mut splits_sum = 0;
+    let mut splits_sum: usize = 0;
     while let Some(split) = walker.step() {
         splits_sum += split;
     }
-    Ok(splits_sum)
-}
-
-#[derive(Debug)]
-struct QuantumBeam {
-    at: (usize, usize),
-    num_timelines: usize,
-}
-
-#[derive(Debug)]
-struct QuantumManifoldWalker<'a> {
-    manifold: &'a Manifold,
-    beams: Vec<QuantumBeam>,
-}
-
-impl<'a> QuantumManifoldWalker<'a> {
-    fn new(manifold: &'a Manifold) -> Self {
-        Self {
-            manifold,
-            beams: vec![QuantumBeam {
-                at: manifold.start,
-                num_timelines: 1,
-            }],
-        }
-    }
-
-    fn step(&mut self) -> Option<usize> {
-        let mut splits = 0;
-        let mut new_beams = Vec::new();
-        let current_beams = mem::take(&mut self.beams);
-        for beam in current_beams {
-            let (next_row, next_column) = (beam.at.0 + 1, beam.at.1);
-
-            // we assume the final row can't have a splitter at the edge
-            if next_row == self.manifold.grid.len() {
-                return None;
-            }
-            let next = (next_row, next_column);
-
-            let Some(location) = self.manifold.get(next) else {
-                continue;
-            };
-
-            match location {
-                Location::Empty => {
-                    new_beams.push(QuantumBeam {
-                        at: next,
-                        num_timelines: beam.num_timelines,
-                    });
-                }
-                Location::Splitter => {
-                    splits += beam.num_timelines;
-                    new_beams.push(QuantumBeam {
-                        at: (next_row, next_column + 1),
-                        num_timelines: beam.num_timelines,
-                    });
-                    new_beams.push(QuantumBeam {
-                        at: (next_row, next_column - 1),
-                        num_timelines: beam.num_timelines,
-                    });
-                }
-            };
-        }
-        let mut new_beams_folded: HashMap<(usize, usize), QuantumBeam> = HashMap::new();
-        for new_beam in new_beams {
-            new_beams_folded
-                .entry(new_beam.at)
-                .and_modify(|x| x.num_timelines += new_beam.num_timelines)
-                .or_insert(new_beam);
-        }
-
-        self.beams = new_beams_folded.into_values().collect();
-
-        Some(splits)
-    }
+    Ok(splits_sum.into())
 }
 
-pub fn part2(input: &str) -> anyhow::Result<usize> {
+pub fn part2(input: &str) -> anyhow::Result<Answer> {
     let manifold: Manifold = input.parse().context("failed to parse input")?;
     let mut walker = QuantumManifoldWalker::new(&manifold);
-    let mut splits_sum = 1;
+    let mut splits_sum = Answer::ONE;
     while let Some(split) = walker.step() {
         splits_sum += split;
     }
@@ -234,4 +90,27 @@ mod tests {
         let result = part2(INPUT).unwrap();
         assert_eq!(result, 40);
     }
+
+    #[test]
+    fn test_beams_leaving_the_sides() {
+        // a splitter on the left edge, and one on the right edge
+        let input = indoc! {"
+            S..
+            ^..
+            .^.
+            ..^
+            ...
+        "};
+        assert_eq!(part1(input).unwrap(), 3);
+        assert_eq!(part2(input).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let err = "S..\n^..\n..\n".parse::<Manifold>().err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: row has 2 cells, expected 3"
+        );
+    }
 }
@@ -1,155 +1,66 @@
-use std::{cmp::Reverse, collections::HashSet, str::FromStr};
+use std::str::FromStr;
 
 use anyhow::Context;
+use aoc_core::{
+    answer::Answer,
+    day8::{Location, connect_all, connect_closest},
+};
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Location {
-    x: u32,
-    y: u32,
-    z: u32,
-}
+use crate::parse::{ParseError, Parser, lines, parse_all, tag, uint};
 
-impl Location {
-    fn distance(&self, other: &Location) -> u64 {
-        (self.x as i64 - other.x as i64).pow(2) as u64
-            + (self.y as i64 - other.y as i64).pow(2) as u64
-            + (self.z as i64 - other.z as i64).pow(2) as u64
-    }
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct JunctionBox {
+    pub(crate) location: Location,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct JunctionBox {
-    location: Location,
+/// `<x>,<y>,<z>`
+fn junction_box<'a>() -> impl Parser<'a, JunctionBox> {
+    let coordinate = || uint::<u32>().map(i64::from);
+    coordinate()
+        .skip(tag(","))
+        .then(coordinate())
+        .skip(tag(","))
+        .then(coordinate())
+        .map(|((x, y), z)| JunctionBox {
+            location: Location::new([x, y, z]),
+        })
 }
 
 impl FromStr for JunctionBox {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut it = s.split(",").fuse();
-        let x: u32 = it
-            .next()
-            .context("no first number")?
-            .parse()
-            .context("failed to parse first number")?;
-        let y: u32 = it
-            .next()
-            .context("no second number")?
-            .parse()
-            .context("failed to parse second number")?;
-        let z: u32 = it
-            .next()
-            .context("no third number")?
-            .parse()
-            .context("failed to parse third number")?;
-        Ok(Self {
-            location: Location { x, y, z },
-        })
+        parse_all(junction_box(), s)
     }
 }
 
-fn get_sorted_distances(boxes: &[JunctionBox]) -> Vec<(u64, (&JunctionBox, &JunctionBox))> {
-    let mut distances = Vec::with_capacity(boxes.len() * boxes.len());
-    for (i, junction_box) in boxes.iter().enumerate() {
-        for other in boxes.iter().skip(i) {
-            if junction_box == other {
-                continue;
-            }
-
-            let distance = junction_box.location.distance(&other.location);
-            distances.push((distance, (junction_box, other)));
-        }
-    }
-
-    distances.sort_by_key(|(d, _)| *d);
-
-    distances
+pub fn parse_boxes(input: &str) -> anyhow::Result<Vec<JunctionBox>> {
+    parse_all(lines(junction_box()), input).context("failed to parse junction boxes")
 }
 
-fn merge_boxes<'a>(
-    circuits: &mut Vec<HashSet<&'a JunctionBox>>,
-    j1: &'a JunctionBox,
-    j2: &'a JunctionBox,
-) -> anyhow::Result<()> {
-    let first_circuit = circuits.iter().position(|c| c.contains(&j1));
-    let second_circuit = circuits.iter().position(|c| c.contains(&j2));
-
-    if let Some(first) = first_circuit
-        && let Some(second) = second_circuit
-    {
-        if second == first {
-            // do nothing
-            return Ok(());
-        }
-        if second > first {
-            let second = circuits.remove(second);
-            let first = circuits.get_mut(first).context("failed to get first")?;
-            first.extend(second);
-        } else {
-            let first = circuits.remove(first);
-            let second = circuits.get_mut(second).context("failed to get second")?;
-            second.extend(first);
-        }
-    } else if let Some(first) = first_circuit {
-        let c = circuits
-            .get_mut(first)
-            .context("failed to get first circuit")?;
-        c.insert(j2);
-    } else if let Some(second) = second_circuit {
-        let c = circuits
-            .get_mut(second)
-            .context("failed to get second circuit")?;
-        c.insert(j1);
-    } else {
-        circuits.push(HashSet::from_iter([j1, j2]));
-    }
-
-    Ok(())
+pub(crate) fn locations(boxes: &[JunctionBox]) -> Vec<Location> {
+    boxes.iter().map(|b| b.location).collect()
 }
 
-pub fn part1(input: &str, num_connections: usize) -> anyhow::Result<usize> {
-    let mut boxes = Vec::new();
-    for line in input.lines() {
-        let junction_box: JunctionBox = line.parse().context("failed to parse line")?;
-        boxes.push(junction_box);
-    }
-    let distances = get_sorted_distances(&boxes);
-
-    let mut circuits: Vec<HashSet<&JunctionBox>> = Vec::new();
-    // for b in &boxes {
-    //     circuits.push(HashSet::from_iter([b]));
-    // }
-
-    for (_, (j1, j2)) in distances.into_iter().take(num_connections) {
-        merge_boxes(&mut circuits, j1, j2).context("failed to merged")?;
-    }
-
-    circuits.sort_by_key(|x| Reverse(x.len()));
-    Ok(circuits.iter().take(3).map(|x| x.len()).product())
+pub fn part1(input: &str, num_connections: usize) -> anyhow::Result<Answer> {
+    let boxes = parse_boxes(input)?;
+    let circuits = connect_closest(&locations(&boxes), num_connections);
+    Ok(circuits
+        .iter()
+        .take(3)
+        .map(|x| Answer::from(x.len()))
+        .product())
 }
 
-pub fn part2(input: &str) -> anyhow::Result<u64> {
-    let mut boxes = Vec::new();
-    for line in input.lines() {
-        let junction_box: JunctionBox = line.parse().context("failed to parse line")?;
-        boxes.push(junction_box);
-    }
-    let distances = get_sorted_distances(&boxes);
-    let mut circuits: Vec<HashSet<&JunctionBox>> = Vec::new();
-    for b in &boxes {
-        circuits.push(HashSet::from_iter([b]));
-    }
-    let mut distance_it = distances.into_iter();
-
-    let mut result = None;
-    while circuits.len() != 1 {
-        let (_, (j1, j2)) = distance_it.next().context("no more boxes to connect")?;
-        merge_boxes(&mut circuits, j1, j2).context("failed to merged")?;
-        result = Some(j1.location.x as u64 * j2.location.x as u64);
-    }
-
-    let result = result.context("no merges happened")?;
-    Ok(result)
+pub fn part2(input: &str) -> anyhow::Result<Answer> {
+    let boxes = parse_boxes(input)?;
+    let (first, second) =
+        connect_all(&locations(&boxes)).context("failed to connect all boxes into one circuit")?;
+    // the coordinates were parsed from `u32`s, so they aren't negative
+    let x = |i: usize| u64::try_from(boxes[i].location[0]).map(Answer::from);
+    Ok(x(first)? * x(second)?)
 }
 
 #[cfg(test)]
@@ -191,4 +102,13 @@ mod tests {
         let result = part2(INPUT).unwrap();
         assert_eq!(result, 25272);
     }
+
+    #[test]
+    fn test_far_apart_boxes() {
+        let input = indoc! {"
+            0,0,0
+            4294967295,4294967295,4294967295
+        "};
+        assert_eq!(part2(input).unwrap(), 0);
+    }
 }
@@ -0,0 +1,144 @@
+use std::{fmt::Display, str::FromStr};
+
+#[cfg(feature = "serde")]
+use anyhow::Context;
+
+#[cfg(feature = "serde")]
+use crate::{day1, day2, day3, day4, day5, day6, day7, day8, runner::Params};
+
+/// How far into a solution `dump` goes before showing what it has
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// The input as the solution sees it
+    Parsed,
+    /// What the solution works out on the way to its answers
+    Intermediate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownStage(String);
+
+impl Display for UnknownStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown stage {:?}, expected parsed or intermediate",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownStage {}
+
+impl FromStr for Stage {
+    type Err = UnknownStage;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parsed" => Ok(Self::Parsed),
+            "intermediate" => Ok(Self::Intermediate),
+            other => Err(UnknownStage(other.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn to_json(value: &impl serde::Serialize) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(value)
+}
+
+/// The models of the given day at `stage`, as pretty printed JSON
+#[cfg(feature = "serde")]
+pub fn dump(day: u8, stage: Stage, input: &str, params: &Params) -> anyhow::Result<String> {
+    // the same trimming as `runner::solve`
+    let input = input.trim_end_matches(['\n', '\r']);
+    let json = match (day, stage) {
+        (1, Stage::Parsed) => to_json(&day1::parse_rotations(input)?),
+        (1, Stage::Intermediate) => {
+            // the dial position after every rotation
+            let mut dial = params.dial()?;
+            let positions: Vec<u64> = day1::parse_rotations(input)?
+                .into_iter()
+                .map(|rotation| {
+                    dial += rotation;
+                    dial.position()
+                })
+                .collect();
+            to_json(&positions)
+        }
+        (2, Stage::Parsed) => to_json(&day2::parse_ranges(input)?),
+        (3, Stage::Parsed) => to_json(&day3::parse_power_banks(input)?),
+        (4, Stage::Parsed) => to_json(&input.parse::<day4::Grid>()?),
+        (4, Stage::Intermediate) => {
+            // the papers removed in every round
+            let mut grid: day4::Grid = input.parse()?;
+            to_json(&grid.peel())
+        }
+        (5, Stage::Parsed) => to_json(&input.parse::<day5::IngredientDatabase>()?),
+        (5, Stage::Intermediate) => {
+            let database: day5::IngredientDatabase = input.parse()?;
+            to_json(&database.merged_ranges())
+        }
+        (6, Stage::Parsed) => to_json(&day6::parse_problems(input)?),
+        // the numbers read down the columns, padded to line up their digits
+        (6, Stage::Intermediate) => to_json(&day6::parse_problems_part2(input)?),
+        (7, Stage::Parsed) => to_json(&input.parse::<day7::Manifold>()?),
+        (8, Stage::Parsed) => to_json(&day8::parse_boxes(input)?),
+        (8, Stage::Intermediate) => {
+            // the junction boxes of every circuit, the largest first. Boxes that weren't connected
+            // to anything are left out
+            let boxes = day8::parse_boxes(input)?;
+            let circuits =
+                aoc_core::day8::connect_closest(&day8::locations(&boxes), params.num_connections);
+            let circuits: Vec<Vec<&day8::JunctionBox>> = circuits
+                .into_iter()
+                .map(|circuit| circuit.into_iter().map(|i| &boxes[i]).collect())
+                .collect();
+            to_json(&circuits)
+        }
+        (1..=8, Stage::Intermediate) => anyhow::bail!("day {day} has no intermediate stage"),
+        (day, _) => anyhow::bail!("there is no puzzle for day {day}"),
+    };
+
+    json.context("failed to serialize")
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_dump() {
+        let input = indoc! {"
+            3-5
+            10-14
+            12-18
+
+            5
+        "};
+        let json = dump(5, Stage::Intermediate, input, &Params::default()).unwrap();
+        let merged: Vec<aoc_core::range::InclusiveRange> = serde_json::from_str(&json).unwrap();
+        assert_eq!(merged.len(), 2);
+        assert_eq!((merged[1].start(), merged[1].end()), (10, 18));
+
+        let json = dump(1, Stage::Intermediate, "L60\nR20\n", &Params::default()).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<u64>>(&json).unwrap(), [90, 10]);
+
+        let params = Params {
+            num_connections: 1,
+            ..Params::default()
+        };
+        let json = dump(8, Stage::Intermediate, "0,0,0\n1,1,1\n10,10,10\n", &params).unwrap();
+        let circuits: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let boxes = dump(8, Stage::Parsed, "0,0,0\n1,1,1\n", &params).unwrap();
+        assert_eq!(
+            circuits,
+            serde_json::json!([serde_json::from_str::<serde_json::Value>(&boxes).unwrap()])
+        );
+
+        let err = dump(3, Stage::Intermediate, "12\n", &Params::default()).unwrap_err();
+        assert_eq!(err.to_string(), "day 3 has no intermediate stage");
+        assert!(dump(9, Stage::Parsed, "", &Params::default()).is_err());
+    }
+}
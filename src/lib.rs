@@ -0,0 +1,16 @@
+pub mod animate;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod dump;
+pub mod parse;
+#[cfg(feature = "python")]
+mod python;
+pub mod render;
+pub mod runner;
+pub mod serve;
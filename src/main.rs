@@ -1,24 +1,117 @@
-// allow because most of the days aren't ran
-#![allow(dead_code)]
-use std::io::{Read, stdin};
+use std::{
+    io::{Read, stderr, stdin, stdout},
+    net::TcpListener,
+};
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
+use anyhow::Context;
+use aoc_core::stats;
+use aoc_rs_2025::{animate, day1, render, runner, serve};
 
-fn read_from_stdin() -> String {
+mod cli;
+
+fn read_from_stdin() -> std::io::Result<String> {
     let mut buffer = String::new();
-    stdin().lock().read_to_string(&mut buffer).unwrap();
-    buffer
+    stdin().lock().read_to_string(&mut buffer)?;
+    Ok(buffer)
 }
 
-fn main() {
-    let input = read_from_stdin();
-    let result = day8::part2(input.trim()).unwrap();
-    println!("{}", result);
+fn main() -> anyhow::Result<()> {
+    let command = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e:#}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    match command {
+        cli::Command::Run {
+            day,
+            part,
+            params,
+            timeout,
+            animation,
+            stats,
+            stream,
+            explain,
+        } => {
+            if stats && !stats::ENABLED {
+                anyhow::bail!("--stats needs the stats feature, build with `--features stats`");
+            }
+            let solution = if stream {
+                stats::reset();
+                runner::solve_streaming(day, part, stdin().lock(), &params)?
+            } else {
+                let input = read_from_stdin().context("failed to read input")?;
+                // on stderr, so stdout only has the answer
+                if let Some(animation) = animation {
+                    animate::animate(day, &input, &animation, &mut stderr().lock())?;
+                }
+                if explain {
+                    anyhow::ensure!(day == 1, "only day 1 can be explained");
+                    let dial = params.dial()?;
+                    day1::explain(&input, dial, params.dial_target, &mut stderr().lock())?;
+                }
+                stats::reset();
+                runner::solve_with_timeout(day, part, input, params, timeout)?
+            };
+            println!("{}", solution.answer);
+            eprintln!("solved in {:?}", solution.elapsed);
+            if stats {
+                eprint!("{}", stats::snapshot());
+            }
+        }
+        cli::Command::Serve { port, timeout } => {
+            let listener = TcpListener::bind(("127.0.0.1", port))
+                .with_context(|| format!("failed to bind port {port}"))?;
+            eprintln!("listening on {}", listener.local_addr()?);
+            serve::serve(listener, timeout)?;
+        }
+        cli::Command::Render { day, params } => {
+            let input = read_from_stdin().context("failed to read input")?;
+            print!("{}", render::render(day, &input, &params)?);
+        }
+        cli::Command::Steps { params, histogram } => {
+            let input = read_from_stdin().context("failed to read input")?;
+            let dial = params.dial()?;
+            if histogram {
+                day1::write_histogram_csv(&input, dial, &mut stdout().lock())?;
+            } else {
+                day1::write_steps_csv(&input, dial, &mut stdout().lock())?;
+            }
+        }
+        cli::Command::Lock { params } => {
+            let input = read_from_stdin().context("failed to read input")?;
+            day1::write_lock_report(&input, params.dial()?, &mut stdout().lock())?;
+        }
+        cli::Command::Starts {
+            counted,
+            count,
+            params,
+        } => {
+            let input = read_from_stdin().context("failed to read input")?;
+            for range in day1::find_starts(&input, params.dial_size, counted, count)?.iter() {
+                println!("{range}");
+            }
+        }
+        cli::Command::Plan { crossings, params } => {
+            let input = read_from_stdin().context("failed to read input")?;
+            print!(
+                "{}",
+                day1::plan_rotations(&input, params.dial()?, crossings)?
+            );
+        }
+        cli::Command::Dump { day, stage, params } => {
+            let input = read_from_stdin().context("failed to read input")?;
+            #[cfg(feature = "serde")]
+            println!("{}", aoc_rs_2025::dump::dump(day, stage, &input, &params)?);
+            #[cfg(not(feature = "serde"))]
+            {
+                let _ = (day, stage, params, input);
+                anyhow::bail!("dump needs the serde feature, build with `--features serde`");
+            }
+        }
+    }
+
+    Ok(())
 }
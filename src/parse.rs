@@ -0,0 +1,584 @@
+//! Parser combinators for the puzzle inputs.
+//!
+//! A parser is anything implementing [`Parser`], which every
+//! `Fn(&mut Cursor) -> Result<T, Failure>` does. Failures leave the cursor anywhere, `or`
+//! backtracks on its own. The ones escaping [`parse_all`] point at the line and column that
+//! didn't parse.
+
+use std::{cell::Cell, fmt::Display, str::FromStr};
+
+use aoc_core::{
+    grid::{Grid, ParseGridError},
+    range::{InclusiveRange, ParseRangeError},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based
+    pub line: usize,
+    /// 1-based, in chars
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(input: &str, failure: Failure) -> Self {
+        let before = &input[..failure.offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: failure.message,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why a parser failed, at a byte offset into the whole input. Parsers that backtrack throw
+/// most of these away, so the line and column are only worked out once one escapes
+/// [`parse_all`] as a [`ParseError`]
+#[derive(Debug, Clone)]
+pub struct Failure {
+    offset: usize,
+    message: String,
+}
+
+pub type ParseResult<T> = Result<T, Failure>;
+
+/// A position in the input, and the end of the region parsers may look at
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            pos: 0,
+            end: text.len(),
+        }
+    }
+
+    /// What's left to parse
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..self.end]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.end
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let taken = &self.rest()[..len];
+        self.pos += len;
+        taken
+    }
+
+    /// Takes the longest prefix of chars matching `f`
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let len = self.rest().find(|c| !f(c)).unwrap_or(self.rest().len());
+        self.advance(len)
+    }
+
+    pub fn error_at(&self, offset: usize, message: impl Into<String>) -> Failure {
+        Failure {
+            offset,
+            message: message.into(),
+        }
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> Failure {
+        self.error_at(self.pos, message)
+    }
+
+    /// An error for the next char not being what we expected
+    fn unexpected(&self, expected: impl Display) -> Failure {
+        match self.peek() {
+            Some(c) => self.error(format!("expected {expected}, found {c:?}")),
+            None => self.error(format!("expected {expected}, found the end")),
+        }
+    }
+}
+
+pub trait Parser<'a, T> {
+    fn parse(&self, cursor: &mut Cursor<'a>) -> ParseResult<T>;
+
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |cursor: &mut Cursor<'a>| self.parse(cursor).map(&f)
+    }
+
+    /// Like `map`, but `f` can fail, pointing the error at where `self` started
+    fn try_map<U, E: Display>(self, f: impl Fn(T) -> Result<U, E>) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |cursor: &mut Cursor<'a>| {
+            let start = *cursor;
+            let value = self.parse(cursor)?;
+            f(value).map_err(|e| start.error(e.to_string()))
+        }
+    }
+
+    /// Parses `self` and then `next`, keeping both
+    fn then<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, (T, U)>
+    where
+        Self: Sized,
+    {
+        move |cursor: &mut Cursor<'a>| Ok((self.parse(cursor)?, next.parse(cursor)?))
+    }
+
+    /// Parses `self` and then `next`, keeping only `self`
+    fn skip<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        self.then(next).map(|(first, _)| first)
+    }
+
+    /// Tries `other` if `self` fails, reporting the error that got further
+    fn or(self, other: impl Parser<'a, T>) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        move |cursor: &mut Cursor<'a>| {
+            let start = *cursor;
+            let first_error = match self.parse(cursor) {
+                Ok(value) => return Ok(value),
+                Err(e) => e,
+            };
+            *cursor = start;
+            other.parse(cursor).map_err(|second_error| {
+                if first_error.offset >= second_error.offset {
+                    first_error
+                } else {
+                    second_error
+                }
+            })
+        }
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&mut Cursor<'a>) -> ParseResult<T>,
+{
+    fn parse(&self, cursor: &mut Cursor<'a>) -> ParseResult<T> {
+        self(cursor)
+    }
+}
+
+/// Runs `parser` on the whole of `input`, which may end with line breaks
+pub fn parse_all<'a, T>(parser: impl Parser<'a, T>, input: &'a str) -> Result<T, ParseError> {
+    let mut cursor = Cursor::new(input);
+    let value = parser
+        .parse(&mut cursor)
+        .map_err(|failure| ParseError::new(input, failure))?;
+    cursor.take_while(|c| c == '\n' || c == '\r');
+    if !cursor.is_empty() {
+        return Err(ParseError::new(input, cursor.unexpected("the end")));
+    }
+    Ok(value)
+}
+
+pub fn tag<'a>(tag: &'static str) -> impl Parser<'a, &'a str> {
+    move |cursor: &mut Cursor<'a>| {
+        if cursor.rest().starts_with(tag) {
+            Ok(cursor.advance(tag.len()))
+        } else {
+            Err(cursor.unexpected(format_args!("{tag:?}")))
+        }
+    }
+}
+
+/// A single char, mapped through `f`. `expected` describes the chars `f` accepts
+pub fn char_map<'a, T>(
+    expected: &'static str,
+    f: impl Fn(char) -> Option<T>,
+) -> impl Parser<'a, T> {
+    move |cursor: &mut Cursor<'a>| {
+        let value = cursor
+            .peek()
+            .and_then(&f)
+            .ok_or_else(|| cursor.unexpected(expected))?;
+        cursor.advance(cursor.peek().map_or(0, char::len_utf8));
+        Ok(value)
+    }
+}
+
+/// An unsigned decimal number
+pub fn uint<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |cursor: &mut Cursor<'a>| {
+        let start = *cursor;
+        let digits = cursor.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(cursor.unexpected("a number"));
+        }
+        digits
+            .parse()
+            .map_err(|_| start.error(format!("{digits} is out of range")))
+    }
+}
+
+/// A decimal number with an optional leading `-`
+pub fn int<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |cursor: &mut Cursor<'a>| {
+        let start = *cursor;
+        if cursor.peek() == Some('-') {
+            cursor.advance(1);
+        }
+        let digits = cursor.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(cursor.unexpected("a number"));
+        }
+        let number = &start.rest()[..cursor.pos - start.pos];
+        number
+            .parse()
+            .map_err(|_| start.error(format!("{number} is out of range")))
+    }
+}
+
+/// A run of non whitespace chars
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    move |cursor: &mut Cursor<'a>| {
+        let word = cursor.take_while(|c| !c.is_whitespace());
+        if word.is_empty() {
+            return Err(cursor.unexpected("a word"));
+        }
+        Ok(word)
+    }
+}
+
+/// `<start>-<end>`, where `start` isn't after `end`
+pub fn range<'a>() -> impl Parser<'a, InclusiveRange> {
+    uint().skip(tag("-")).then(uint()).try_map(|(start, end)| {
+        InclusiveRange::new(start, end).ok_or(ParseRangeError::StartAfterEnd { start, end })
+    })
+}
+
+/// As many `item`s as there are in a row, maybe none
+pub fn many<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |cursor: &mut Cursor<'a>| {
+        let mut items = Vec::new();
+        loop {
+            let before = *cursor;
+            match item.parse(cursor) {
+                Ok(value) => items.push(value),
+                Err(_) => {
+                    *cursor = before;
+                    return Ok(items);
+                }
+            }
+        }
+    }
+}
+
+/// At least one `item`, with `separator` between every two
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |cursor: &mut Cursor<'a>| {
+        let mut items = vec![item.parse(cursor)?];
+        loop {
+            let before_separator = *cursor;
+            if separator.parse(cursor).is_err() {
+                *cursor = before_separator;
+                return Ok(items);
+            }
+            items.push(item.parse(cursor)?);
+        }
+    }
+}
+
+/// The `item`s on the rest of the line, separated by any amount of spaces
+pub fn words<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |cursor: &mut Cursor<'a>| {
+        let mut items = Vec::new();
+        loop {
+            cursor.take_while(|c| c == ' ');
+            if matches!(cursor.peek(), None | Some('\n' | '\r')) {
+                return Ok(items);
+            }
+            items.push(item.parse(cursor)?);
+        }
+    }
+}
+
+/// `item` followed by the end of its line, which is consumed
+pub fn line<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |cursor: &mut Cursor<'a>| {
+        let value = item.parse(cursor)?;
+        if cursor.rest().starts_with("\r\n") {
+            cursor.advance(2);
+        } else if cursor.rest().starts_with('\n') {
+            cursor.advance(1);
+        } else if !cursor.is_empty() {
+            return Err(cursor.unexpected("the end of the line"));
+        }
+        Ok(value)
+    }
+}
+
+/// An `item` on every line up to the end of the region
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    let line = line(item);
+    move |cursor: &mut Cursor<'a>| {
+        let mut items = Vec::new();
+        while !cursor.is_empty() {
+            items.push(line.parse(cursor)?);
+        }
+        Ok(items)
+    }
+}
+
+/// `item` on the lines up to the next blank line, which must all be consumed.
+/// The blank line is skipped
+pub fn section<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |cursor: &mut Cursor<'a>| {
+        let rest = cursor.rest();
+        let (len, blank_len) = [("\n\n", 1), ("\r\n\r\n", 2)]
+            .into_iter()
+            .filter_map(|(blank, newline_len)| {
+                rest.find(blank)
+                    .map(|i| (i + newline_len, blank.len() - newline_len))
+            })
+            .min()
+            .unwrap_or((rest.len(), 0));
+
+        let mut region = *cursor;
+        region.end = region.pos + len;
+        let value = item.parse(&mut region)?;
+        if !region.is_empty() {
+            return Err(region.unexpected("the end of the section"));
+        }
+        cursor.advance(len + blank_len);
+        Ok(value)
+    }
+}
+
+/// Splits the rest of the line into cells `widths` bytes wide, with `gap` bytes between them.
+/// The last cell reached by the end of the line may be shorter than its width
+pub fn fixed_width<'a>(widths: &[usize], gap: usize) -> impl Parser<'a, Vec<&'a str>> {
+    move |cursor: &mut Cursor<'a>| {
+        let start = *cursor;
+        let line_len = cursor
+            .rest()
+            .find(['\r', '\n'])
+            .unwrap_or(cursor.rest().len());
+        let line = &cursor.rest()[..line_len];
+
+        let mut cells = Vec::with_capacity(widths.len());
+        let mut offset = 0;
+        for (column, width) in widths.iter().enumerate() {
+            if offset >= line.len() {
+                return Err(start.error_at(
+                    start.pos + line.len(),
+                    format!("column {column} is missing"),
+                ));
+            }
+            let end = (offset + width).min(line.len());
+            let cell = line.get(offset..end).ok_or_else(|| {
+                start.error_at(
+                    start.pos + offset,
+                    format!("column {column} is not on a char boundary"),
+                )
+            })?;
+            cells.push(cell);
+            offset = end + gap;
+        }
+
+        cursor.advance(line_len);
+        Ok(cells)
+    }
+}
+
+/// A grid of chars up to the end of the region, every char mapped through `f` with its
+/// `(row, column)`
+pub fn char_grid<'a, T>(f: impl Fn((usize, usize), char) -> Option<T>) -> impl Parser<'a, Grid<T>> {
+    move |cursor: &mut Cursor<'a>| {
+        let rest = cursor.rest();
+        let grid = Grid::parse(rest, &f).map_err(|e| {
+            // the offset of `row` inside `rest`
+            let row_offset =
+                |row: usize| -> usize { rest.split_inclusive('\n').take(row).map(str::len).sum() };
+            match e {
+                ParseGridError::InvalidChar {
+                    c,
+                    at: (row, column),
+                } => {
+                    let line = &rest[row_offset(row)..];
+                    let column_offset = line.char_indices().nth(column).map_or(0, |(i, _)| i);
+                    cursor.error_at(
+                        cursor.pos + row_offset(row) + column_offset,
+                        format!("{c:?} is not a valid cell"),
+                    )
+                }
+                ParseGridError::RaggedRow { row, len, expected } => cursor.error_at(
+                    cursor.pos + row_offset(row),
+                    format!("row has {len} cells, expected {expected}"),
+                ),
+            }
+        })?;
+        cursor.advance(rest.len());
+        Ok(grid)
+    }
+}
+
+/// Like `char_grid`, also returning the position of the one `marker` char, which `f` sees too
+pub fn char_grid_with_marker<'a, T>(
+    marker: char,
+    f: impl Fn((usize, usize), char) -> Option<T>,
+) -> impl Parser<'a, (Grid<T>, (usize, usize))> {
+    move |cursor: &mut Cursor<'a>| {
+        let start = cursor.pos;
+        let found = Cell::new(None);
+        let grid = char_grid(|at, c| {
+            if c == marker {
+                found.set(Some(at));
+            }
+            f(at, c)
+        })
+        .parse(cursor)?;
+        match found.get() {
+            Some(at) => Ok((grid, at)),
+            None => Err(cursor.error_at(start, format!("no {marker:?} found"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        let point = || separated(int::<i32>(), tag(","));
+        assert_eq!(parse_all(point(), "1,-2,3\n"), Ok(vec![1, -2, 3]));
+
+        let err = parse_all(point(), "1,x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: expected a number, found 'x'"
+        );
+        let err = parse_all(uint::<u8>(), "256").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: 256 is out of range");
+        let err = parse_all(point(), "1,2 3").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 4: expected the end, found ' '"
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        let ranges = || separated(range(), tag(","));
+        let parsed = parse_all(ranges(), "1-2,3-3").unwrap();
+        assert_eq!(
+            parsed.iter().map(|x| x.len()).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        let err = parse_all(ranges(), "1-2,5-3").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 5: range start 5 is after its end 3"
+        );
+    }
+
+    #[test]
+    fn test_lines_and_sections() {
+        let input = "1-2\n3-4\n\n5\n6\n";
+        let range = uint::<u8>().skip(tag("-")).then(uint::<u8>());
+        let parser = section(lines(range)).then(lines(uint::<u8>()));
+        assert_eq!(
+            parse_all(parser, input),
+            Ok((vec![(1, 2), (3, 4)], vec![5, 6]))
+        );
+
+        let parser = lines(words(word()));
+        assert_eq!(
+            parse_all(parser, "a  bc \r\n d\n"),
+            Ok(vec![vec!["a", "bc"], vec!["d"]])
+        );
+
+        let err = parse_all(lines(uint::<u8>()), "1\n2\n\n3").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: expected a number, found '\\n'"
+        );
+    }
+
+    #[test]
+    fn test_or() {
+        let parser = || lines(uint::<u8>().map(Some).or(tag("-").map(|_| None)));
+        assert_eq!(parse_all(parser(), "1\n-\n"), Ok(vec![Some(1), None]));
+        // the error of the branch that got further
+        let err = parse_all(parser(), "1\n12x\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected the end of the line, found 'x'"
+        );
+    }
+
+    #[test]
+    fn test_long_input() {
+        // the failures `many` and `or` throw away mustn't cost a pass over the input each
+        let input = "12345\n".repeat(300_000);
+        let digit = || char_map("a digit", |c| c.to_digit(10));
+        let parser = lines(many(digit()).or(tag("-").map(|_| Vec::new())));
+        let start = std::time::Instant::now();
+        assert_eq!(parse_all(parser, &input).unwrap().len(), 300_000);
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_fixed_width() {
+        let parser = || lines(fixed_width(&[3, 2], 1));
+        assert_eq!(
+            parse_all(parser(), "123 45\n 1  6\n12  4"),
+            Ok(vec![vec!["123", "45"], vec![" 1 ", "6"], vec!["12 ", "4"]])
+        );
+        let err = parse_all(parser(), "123").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 4: column 1 is missing");
+    }
+
+    #[test]
+    fn test_char_grid() {
+        let digit = |_, c: char| c.to_digit(10);
+        let grid = parse_all(char_grid(digit), "12\n34\n").unwrap();
+        assert_eq!(grid[(1, 0)], 3);
+
+        let err = parse_all(char_grid(digit), "12\n3é\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 2: 'é' is not a valid cell");
+        let err = parse_all(char_grid(digit), "12\n3\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: row has 1 cells, expected 2"
+        );
+
+        let parser = || char_grid_with_marker('S', |_, c| Some(c));
+        assert_eq!(parse_all(parser(), ".S\n..").unwrap().1, (0, 1));
+        let err = parse_all(parser(), "..").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: no 'S' found");
+    }
+}
@@ -0,0 +1,376 @@
+//! The `aoc_rs_2025` Python extension module, built with `maturin` and the `python` feature.
+//!
+//! Every day is a submodule with its `part1` and `part2`, and the days with interesting
+//! intermediate structures expose their models as classes.
+
+use pyo3::prelude::*;
+
+#[pymodule]
+mod aoc_rs_2025 {
+    use pyo3::{exceptions::PyValueError, prelude::*};
+
+    use aoc_core::answer::{Answer, BigUint};
+
+    use crate::runner::{self, Params};
+
+    fn value_error(e: impl std::fmt::Display) -> PyErr {
+        // `:#` so anyhow errors include their whole context chain
+        PyValueError::new_err(format!("{e:#}"))
+    }
+
+    /// Answers become Python ints, however large they are
+    fn answer(result: anyhow::Result<Answer>) -> PyResult<BigUint> {
+        result.map(BigUint::from).map_err(value_error)
+    }
+
+    /// Runs any day and part, the same way the CLI does
+    #[pyfunction]
+    #[pyo3(signature = (
+        day,
+        part,
+        input,
+        num_connections = Params::default().num_connections,
+        dial_size = Params::default().dial_size,
+        dial_start = Params::default().dial_start,
+        dial_target = Params::default().dial_target,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn solve(
+        py: Python<'_>,
+        day: u8,
+        part: u8,
+        input: &str,
+        num_connections: usize,
+        dial_size: u64,
+        dial_start: u64,
+        dial_target: u64,
+    ) -> PyResult<String> {
+        let params = Params {
+            num_connections,
+            dial_size,
+            dial_start,
+            dial_target,
+        };
+        py.detach(|| runner::solve(day, part, input, &params))
+            .map_err(value_error)
+    }
+
+    #[pymodule]
+    mod day1 {
+        use super::*;
+
+        use aoc_core::day1::Dial;
+
+        fn dial(size: u64, start: u64) -> PyResult<Dial> {
+            Params {
+                dial_size: size,
+                dial_start: start,
+                ..Params::default()
+            }
+            .dial()
+            .map_err(value_error)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (input, size = Dial::DEFAULT_SIZE, start = Dial::DEFAULT_START))]
+        fn part1(input: &str, size: u64, start: u64) -> PyResult<BigUint> {
+            answer(crate::day1::part1(input, dial(size, start)?))
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (input, size = Dial::DEFAULT_SIZE, start = Dial::DEFAULT_START))]
+        fn part2(input: &str, size: u64, start: u64) -> PyResult<BigUint> {
+            answer(crate::day1::part2(input, dial(size, start)?))
+        }
+    }
+
+    #[pymodule]
+    mod day2 {
+        use super::*;
+
+        #[pyfunction]
+        fn part1(input: &str) -> PyResult<BigUint> {
+            answer(crate::day2::part1(input))
+        }
+
+        #[pyfunction]
+        fn part2(input: &str) -> PyResult<BigUint> {
+            answer(crate::day2::part2(input))
+        }
+    }
+
+    #[pymodule]
+    mod day3 {
+        use super::*;
+
+        #[pyfunction]
+        fn part1(input: &str) -> PyResult<BigUint> {
+            answer(crate::day3::part1(input))
+        }
+
+        #[pyfunction]
+        fn part2(input: &str) -> PyResult<BigUint> {
+            answer(crate::day3::part2(input))
+        }
+    }
+
+    #[pymodule]
+    mod day4 {
+        use super::*;
+
+        #[pyclass(name = "Grid")]
+        struct PyGrid(crate::day4::Grid);
+
+        #[pymethods]
+        impl PyGrid {
+            #[new]
+            fn new(input: &str) -> PyResult<Self> {
+                input.parse().map(Self).map_err(value_error)
+            }
+
+            /// `(row, column)` of every paper with less than 4 adjacent papers
+            fn accessible_papers(&self) -> Vec<(usize, usize)> {
+                self.0.get_accessible_papers()
+            }
+
+            fn remove_papers(&mut self, papers: Vec<(usize, usize)>) {
+                self.0.remove_papers(&papers);
+            }
+
+            /// Removes accessible papers until none are left, returning the papers of each round
+            fn peel(&mut self) -> Vec<Vec<(usize, usize)>> {
+                self.0.peel()
+            }
+        }
+
+        #[pyfunction]
+        fn part1(input: &str) -> PyResult<BigUint> {
+            answer(crate::day4::part1(input))
+        }
+
+        #[pyfunction]
+        fn part2(input: &str) -> PyResult<BigUint> {
+            answer(crate::day4::part2(input))
+        }
+    }
+
+    #[pymodule]
+    mod day5 {
+        use super::*;
+
+        #[pyclass(name = "IngredientDatabase")]
+        struct PyIngredientDatabase(crate::day5::IngredientDatabase);
+
+        #[pymethods]
+        impl PyIngredientDatabase {
+            #[new]
+            fn new(input: &str) -> PyResult<Self> {
+                input.parse().map(Self).map_err(value_error)
+            }
+
+            /// The fresh ID ranges as inclusive `(start, end)` pairs
+            #[getter]
+            fn ranges(&self) -> Vec<(u64, u64)> {
+                self.0
+                    .ingredient_id_ranges()
+                    .iter()
+                    .map(|range| (range.start(), range.end()))
+                    .collect()
+            }
+
+            #[getter]
+            fn ingredients(&self) -> Vec<u64> {
+                self.0.ingredients().to_vec()
+            }
+
+            fn is_fresh(&self, id: u64) -> bool {
+                self.0.is_fresh(&id)
+            }
+
+            /// The fresh ID ranges after merging the overlapping ones
+            fn merged_ranges(&self) -> Vec<(u64, u64)> {
+                self.0
+                    .merged_ranges()
+                    .into_iter()
+                    .map(|range| (range.start(), range.end()))
+                    .collect()
+            }
+        }
+
+        #[pyfunction]
+        fn part1(input: &str) -> PyResult<BigUint> {
+            answer(crate::day5::part1(input))
+        }
+
+        #[pyfunction]
+        fn part2(input: &str) -> PyResult<BigUint> {
+            answer(crate::day5::part2(input))
+        }
+    }
+
+    #[pymodule]
+    mod day6 {
+        use super::*;
+
+        #[pyfunction]
+        fn part1(input: &str) -> PyResult<BigUint> {
+            answer(crate::day6::part1(input))
+        }
+
+        #[pyfunction]
+        fn part2(input: &str) -> PyResult<BigUint> {
+            answer(crate::day6::part2(input))
+        }
+    }
+
+    #[pymodule]
+    mod day7 {
+        use std::collections::BTreeMap;
+
+        use super::*;
+        use aoc_core::day7::{ManifoldWalker, QuantumManifoldWalker};
+
+        #[pyclass(name = "Manifold")]
+        struct PyManifold(crate::day7::Manifold);
+
+        #[pymethods]
+        impl PyManifold {
+            #[new]
+            fn new(input: &str) -> PyResult<Self> {
+                input.parse().map(Self).map_err(value_error)
+            }
+
+            /// `(row, column)` of the beam source
+            #[getter]
+            fn start(&self) -> (usize, usize) {
+                self.0.start()
+            }
+
+            /// The sorted beam positions on every row, starting with just the source
+            fn beams(&self) -> Vec<Vec<(usize, usize)>> {
+                let mut walker = ManifoldWalker::new(&self.0);
+                let mut rows = vec![walker.beams().to_vec()];
+                while walker.step().is_some() {
+                    rows.push(walker.beams().to_vec());
+                }
+                rows
+            }
+
+            /// The number of timelines reaching every beam position, row by row
+            fn timelines(&self) -> Vec<BTreeMap<(usize, usize), BigUint>> {
+                let mut walker = QuantumManifoldWalker::new(&self.0);
+                let to_map = |walker: &QuantumManifoldWalker| {
+                    walker
+                        .beams()
+                        .iter()
+                        .map(|beam| (beam.at, beam.num_timelines.clone().into()))
+                        .collect()
+                };
+                let mut rows = vec![to_map(&walker)];
+                while walker.step().is_some() {
+                    rows.push(to_map(&walker));
+                }
+                rows
+            }
+        }
+
+        #[pyfunction]
+        fn part1(input: &str) -> PyResult<BigUint> {
+            answer(crate::day7::part1(input))
+        }
+
+        #[pyfunction]
+        fn part2(input: &str) -> PyResult<BigUint> {
+            answer(crate::day7::part2(input))
+        }
+    }
+
+    #[pymodule]
+    mod day8 {
+        use aoc_core::day8::{Location, connect_closest};
+
+        use super::*;
+
+        fn locations(input: &str) -> PyResult<Vec<Location>> {
+            let boxes = crate::day8::parse_boxes(input).map_err(value_error)?;
+            Ok(crate::day8::locations(&boxes))
+        }
+
+        /// The circuits made by connecting the `num_connections` closest boxes, largest first.
+        /// Every box is an `(x, y, z)` tuple
+        #[pyfunction]
+        fn circuits(input: &str, num_connections: usize) -> PyResult<Vec<Vec<(i64, i64, i64)>>> {
+            let locations = locations(input)?;
+            let circuits = connect_closest(&locations, num_connections);
+            Ok(circuits
+                .into_iter()
+                .map(|circuit| {
+                    circuit
+                        .into_iter()
+                        .map(|i| locations[i].coords.into())
+                        .collect()
+                })
+                .collect())
+        }
+
+        /// The sizes of the circuits made by connecting the `num_connections` closest boxes,
+        /// largest first
+        #[pyfunction]
+        fn circuit_sizes(input: &str, num_connections: usize) -> PyResult<Vec<usize>> {
+            let circuits = connect_closest(&locations(input)?, num_connections);
+            Ok(circuits.iter().map(|circuit| circuit.len()).collect())
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (input, num_connections = Params::default().num_connections))]
+        fn part1(input: &str, num_connections: usize) -> PyResult<BigUint> {
+            answer(crate::day8::part1(input, num_connections))
+        }
+
+        #[pyfunction]
+        fn part2(input: &str) -> PyResult<BigUint> {
+            answer(crate::day8::part2(input))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::prelude::*;
+
+    use super::aoc_rs_2025;
+
+    #[test]
+    fn test_module() {
+        pyo3::append_to_inittab!(aoc_rs_2025);
+        Python::initialize();
+        Python::attach(|py| {
+            py.run(
+                cr#"
+import aoc_rs_2025
+
+db = aoc_rs_2025.day5.IngredientDatabase("3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32")
+assert db.merged_ranges() == [(3, 5), (10, 20)], db.merged_ranges()
+assert db.is_fresh(5) and not db.is_fresh(8)
+
+boxes = "0,0,0\n1,1,1\n10,10,10"
+# only the boxes that got connected make circuits
+assert aoc_rs_2025.day8.circuit_sizes(boxes, 1) == [2]
+assert aoc_rs_2025.day8.circuits(boxes, 1) == [[(0, 0, 0), (1, 1, 1)]]
+
+assert aoc_rs_2025.day1.part2("L68\nL30\nR48", size=100, start=50) == 2
+assert aoc_rs_2025.solve(2, 1, "11-22") == "33"
+try:
+    aoc_rs_2025.day1.part1("X1")
+except ValueError as e:
+    assert "expected 'L' or 'R'" in str(e), e
+else:
+    assert False, "expected a ValueError"
+"#,
+                None,
+                None,
+            )
+            .unwrap();
+        });
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+};
+
+use anyhow::Context;
+use aoc_core::{
+    answer::Answer,
+    day4::Cell,
+    day7::{Location, QuantumManifoldWalker},
+    day8::{connect_closest_sorted, get_sorted_distances},
+    geometry::BoundingBox,
+    grid::Grid,
+};
+
+use crate::{day4, day7, day8, runner::Params};
+
+/// The side of a grid cell, in pixels
+const CELL: f64 = 20.0;
+/// The side of the day8 drawing, in pixels
+const SIZE: f64 = 800.0;
+const MARGIN: f64 = 20.0;
+
+/// An SVG document that shapes are appended to
+struct Svg {
+    width: f64,
+    height: f64,
+    body: String,
+}
+
+impl Svg {
+    fn new(width: f64, height: f64) -> Self {
+        Self {
+            width,
+            height,
+            body: String::new(),
+        }
+    }
+
+    fn push(&mut self, shape: fmt::Arguments) {
+        self.body
+            .write_fmt(shape)
+            .expect("writing to a string can't fail");
+        self.body.push('\n');
+    }
+
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str) {
+        self.push(format_args!(
+            r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{fill}"/>"#
+        ));
+    }
+
+    fn line(&mut self, from: (f64, f64), to: (f64, f64), stroke: &str, width: f64) {
+        self.push(format_args!(
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{stroke}" stroke-width="{width}"/>"#,
+            from.0, from.1, to.0, to.1
+        ));
+    }
+
+    fn circle(&mut self, center: (f64, f64), radius: f64, fill: &str) {
+        self.push(format_args!(
+            r#"<circle cx="{}" cy="{}" r="{radius}" fill="{fill}"/>"#,
+            center.0, center.1
+        ));
+    }
+
+    fn polygon(&mut self, points: &[(f64, f64)], fill: &str) {
+        let points: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        self.push(format_args!(
+            r#"<polygon points="{}" fill="{fill}"/>"#,
+            points.join(" ")
+        ));
+    }
+
+    fn text(&mut self, at: (f64, f64), size: f64, text: impl fmt::Display) {
+        self.push(format_args!(
+            r#"<text x="{}" y="{}" font-size="{size}" font-family="monospace" text-anchor="middle">{text}</text>"#,
+            at.0, at.1
+        ));
+    }
+
+    fn finish(self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n{}</svg>\n",
+            self.body,
+            w = self.width,
+            h = self.height,
+        )
+    }
+}
+
+/// Spreads `i` of `count` from blue to red
+fn gradient(i: usize, count: usize) -> String {
+    let t = i as f64 / count.saturating_sub(1).max(1) as f64;
+    format!("hsl({:.0}, 75%, 50%)", 240.0 * (1.0 - t))
+}
+
+/// A colour for the `i`th of an unknown number of groups, far from the ones before it
+fn distinct(i: usize) -> String {
+    // the golden angle keeps neighbouring hues apart however many there are
+    format!("hsl({:.0}, 70%, 45%)", (i as f64 * 137.508) % 360.0)
+}
+
+fn cell_center((row, column): (usize, usize)) -> (f64, f64) {
+    ((column as f64 + 0.5) * CELL, (row as f64 + 0.5) * CELL)
+}
+
+fn grid_svg<T>(grid: &Grid<T>) -> Svg {
+    Svg::new(grid.width() as f64 * CELL, grid.height() as f64 * CELL)
+}
+
+/// The papers coloured by the round they were removed in, from blue to red.
+/// Papers that are never accessible stay grey
+fn render_day4(grid: &mut day4::Grid) -> String {
+    let mut svg = grid_svg(grid.cells());
+    let papers: Vec<_> = grid
+        .cells()
+        .iter()
+        .filter(|(_, cell)| **cell == Cell::Paper)
+        .map(|(at, _)| at)
+        .collect();
+
+    let rounds = grid.peel();
+    let mut removed_in = BTreeMap::new();
+    for (round, removed) in rounds.iter().enumerate() {
+        for at in removed {
+            removed_in.insert(*at, round);
+        }
+    }
+
+    for (row, column) in papers {
+        let fill = match removed_in.get(&(row, column)) {
+            Some(round) => gradient(*round, rounds.len()),
+            None => "grey".to_string(),
+        };
+        let (x, y) = (column as f64 * CELL, row as f64 * CELL);
+        svg.rect(x + 1.0, y + 1.0, CELL - 2.0, CELL - 2.0, &fill);
+    }
+
+    svg.finish()
+}
+
+/// The paths of the beams, and above every splitter the number of timelines that hit it
+fn render_day7(manifold: &day7::Manifold) -> String {
+    let grid = manifold.grid();
+    let mut svg = grid_svg(grid);
+    let mut hits: BTreeMap<(usize, usize), Answer> = BTreeMap::new();
+    let mut beams = Vec::new();
+
+    let mut walker = QuantumManifoldWalker::new(manifold);
+    loop {
+        for beam in walker.beams() {
+            beams.push(beam.at);
+            let below = (beam.at.0 + 1, beam.at.1);
+            if grid.get(below) == Some(&Location::Splitter) {
+                *hits.entry(below).or_default() += beam.num_timelines.clone();
+            }
+        }
+        if walker.step().is_none() {
+            break;
+        }
+    }
+
+    for at in beams {
+        let (x, y) = cell_center(at);
+        svg.line((x, y - CELL / 2.0), (x, y + CELL / 2.0), "gold", 3.0);
+    }
+    for (at, location) in grid.iter() {
+        if *location != Location::Splitter {
+            continue;
+        }
+        let (x, y) = cell_center(at);
+        if hits.contains_key(&at) {
+            // the beam leaving to both sides
+            svg.line((x - CELL, y), (x + CELL, y), "gold", 3.0);
+        }
+        let half = CELL / 2.0 - 2.0;
+        svg.polygon(
+            &[(x, y - half), (x + half, y + half), (x - half, y + half)],
+            "steelblue",
+        );
+    }
+    for (at, count) in hits {
+        let (x, y) = cell_center(at);
+        svg.text((x, y - CELL / 2.0 - 2.0), CELL / 2.0, count);
+    }
+    let (x, y) = cell_center(manifold.start());
+    svg.circle((x, y), CELL / 3.0, "crimson");
+
+    svg.finish()
+}
+
+/// The junction boxes seen from above, on their x and y coordinates. Boxes are coloured by
+/// their circuit after `num_connections` connections, and every connection is drawn
+fn render_day8(boxes: &[day8::JunctionBox], num_connections: usize) -> String {
+    let mut svg = Svg::new(SIZE + 2.0 * MARGIN, SIZE + 2.0 * MARGIN);
+    let locations = day8::locations(boxes);
+    let Some(bounding_box) = BoundingBox::from_points(&locations) else {
+        return svg.finish();
+    };
+
+    let [width, height, _] = bounding_box.size();
+    let scale = SIZE / (width.max(height) as f64 - 1.0).max(1.0);
+    let project = |i: usize| {
+        let location = locations[i];
+        (
+            MARGIN + location[0].abs_diff(bounding_box.min[0]) as f64 * scale,
+            MARGIN + location[1].abs_diff(bounding_box.min[1]) as f64 * scale,
+        )
+    };
+
+    let sorted_distances = get_sorted_distances(&locations);
+    for &(_, i, j) in sorted_distances.iter().take(num_connections) {
+        svg.line(project(i), project(j), "darkgrey", 1.0);
+    }
+
+    let mut colours = vec!["grey".to_string(); locations.len()];
+    for (circuit, members) in connect_closest_sorted(&sorted_distances, num_connections)
+        .iter()
+        .enumerate()
+    {
+        for i in members {
+            colours[*i] = distinct(circuit);
+        }
+    }
+    for (i, colour) in colours.iter().enumerate() {
+        svg.circle(project(i), 4.0, colour);
+    }
+
+    svg.finish()
+}
+
+/// Draws the puzzle of the given day as an SVG image
+pub fn render(day: u8, input: &str, params: &Params) -> anyhow::Result<String> {
+    match day {
+        4 => {
+            let mut grid: day4::Grid = input.parse().context("failed to parse grid")?;
+            Ok(render_day4(&mut grid))
+        }
+        7 => {
+            let manifold: day7::Manifold = input.parse().context("failed to parse input")?;
+            Ok(render_day7(&manifold))
+        }
+        8 => {
+            let boxes = day8::parse_boxes(input)?;
+            Ok(render_day8(&boxes, params.num_connections))
+        }
+        day => anyhow::bail!("day {day} can't be rendered, only days 4, 7 and 8 can"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_render() {
+        let grid = indoc! {"
+            @@@
+            @@@
+            @.@
+        "};
+        let svg = render(4, grid, &Params::default()).unwrap();
+        assert!(svg.starts_with("<svg"));
+        // the page and 8 papers, peeled in 2 rounds
+        assert_eq!(svg.matches("<rect").count(), 9);
+        assert!(svg.contains("hsl(240, 75%, 50%)"));
+        assert!(svg.contains("hsl(0, 75%, 50%)"));
+
+        let manifold = indoc! {"
+            ..S..
+            .....
+            ..^..
+            .....
+            .^.^.
+            .....
+        "};
+        let svg = render(7, manifold, &Params::default()).unwrap();
+        assert_eq!(svg.matches("<polygon").count(), 3);
+        // every splitter is hit by a single timeline
+        assert_eq!(svg.matches(">1</text>").count(), 3);
+
+        let boxes = "0,0,0\n1,1,1\n10,10,10\n";
+        let params = Params {
+            num_connections: 1,
+            ..Params::default()
+        };
+        let svg = render(8, boxes, &params).unwrap();
+        assert_eq!(svg.matches("<line").count(), 1);
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert_eq!(svg.matches(r#"fill="grey""#).count(), 1);
+
+        assert!(render(1, "", &Params::default()).is_err());
+    }
+}
@@ -0,0 +1,326 @@
+use std::{
+    fmt::Display,
+    io::BufRead,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use aoc_core::day1::Dial;
+
+use crate::{day1, day2, day3, day4, day5, day6, day7, day8};
+
+/// Extra knobs some puzzles take besides their input
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// How many of the closest junction box pairs day8 part1 connects
+    pub num_connections: usize,
+    /// How many numbers the day1 dial has
+    pub dial_size: u64,
+    /// Where the day1 dial points before the first rotation
+    pub dial_start: u64,
+    /// The position day1 counts landings on and visits to, instead of zero
+    pub dial_target: u64,
+}
+
+impl Params {
+    pub fn dial(&self) -> anyhow::Result<Dial> {
+        Dial::with_size(self.dial_size, self.dial_start).with_context(|| {
+            format!(
+                "a dial of size {} can't start at {}, it needs at least 2 numbers and to start \
+                 on one of them",
+                self.dial_size, self.dial_start
+            )
+        })
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            num_connections: 1000,
+            dial_size: Dial::DEFAULT_SIZE,
+            dial_start: Dial::DEFAULT_START,
+            dial_target: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Solution {
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub enum SolveError {
+    UnknownPuzzle { day: u8, part: u8 },
+    TimedOut(Duration),
+    Panicked,
+    Failed(anyhow::Error),
+}
+
+impl SolveError {
+    /// A short machine readable name for the error
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SolveError::UnknownPuzzle { .. } => "unknown_puzzle",
+            SolveError::TimedOut(_) => "timed_out",
+            SolveError::Panicked => "panicked",
+            SolveError::Failed(_) => "failed",
+        }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::UnknownPuzzle { day, part } => {
+                write!(f, "there is no solution for day {day} part {part}")
+            }
+            SolveError::TimedOut(timeout) => write!(f, "solution timed out after {timeout:?}"),
+            SolveError::Panicked => write!(f, "solution panicked"),
+            // `:#` so the whole context chain is included
+            SolveError::Failed(e) => write!(f, "{e:#}"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+fn is_known_puzzle(day: u8, part: u8) -> bool {
+    (1..=8).contains(&day) && (1..=2).contains(&part)
+}
+
+/// Runs the solution of the given day and part on the input
+pub fn solve(day: u8, part: u8, input: &str, params: &Params) -> Result<String, SolveError> {
+    // the trailing newline would otherwise end up in the last number of single line inputs
+    let input = input.trim_end_matches(['\n', '\r']);
+    let answer = match (day, part) {
+        (1, 1) => params
+            .dial()
+            .and_then(|dial| day1::landings(input, dial, params.dial_target))
+            .map(|x| x.to_string()),
+        (1, 2) => params
+            .dial()
+            .and_then(|dial| day1::visits(input, dial, params.dial_target))
+            .map(|x| x.to_string()),
+        (2, 1) => day2::part1(input).map(|x| x.to_string()),
+        (2, 2) => day2::part2(input).map(|x| x.to_string()),
+        (3, 1) => day3::part1(input).map(|x| x.to_string()),
+        (3, 2) => day3::part2(input).map(|x| x.to_string()),
+        (4, 1) => day4::part1(input).map(|x| x.to_string()),
+        (4, 2) => day4::part2(input).map(|x| x.to_string()),
+        (5, 1) => day5::part1(input).map(|x| x.to_string()),
+        (5, 2) => day5::part2(input).map(|x| x.to_string()),
+        (6, 1) => day6::part1(input).map(|x| x.to_string()),
+        (6, 2) => day6::part2(input).map(|x| x.to_string()),
+        (7, 1) => day7::part1(input).map(|x| x.to_string()),
+        (7, 2) => day7::part2(input).map(|x| x.to_string()),
+        (8, 1) => day8::part1(input, params.num_connections).map(|x| x.to_string()),
+        (8, 2) => day8::part2(input).map(|x| x.to_string()),
+        (day, part) => return Err(SolveError::UnknownPuzzle { day, part }),
+    };
+
+    answer.map_err(SolveError::Failed)
+}
+
+/// The threads of [`solve_with_timeout`] that haven't finished yet
+static RUNNING_SOLUTIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts a thread in [`RUNNING_SOLUTIONS`] for as long as it lives, panicking or not
+struct RunningSolution;
+
+impl RunningSolution {
+    fn start() -> Self {
+        RUNNING_SOLUTIONS.fetch_add(1, Ordering::Relaxed);
+        Self
+    }
+}
+
+impl Drop for RunningSolution {
+    fn drop(&mut self) {
+        RUNNING_SOLUTIONS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// How many solutions started by [`solve_with_timeout`] are still running, counting the ones
+/// that timed out and are left running in the background
+pub fn running_solutions() -> usize {
+    RUNNING_SOLUTIONS.load(Ordering::Relaxed)
+}
+
+/// Like [`solve`], but gives up waiting after `timeout`.
+///
+/// The solution runs on its own thread, which is left running in the background
+/// if it times out since there is no way to cancel it.
+pub fn solve_with_timeout(
+    day: u8,
+    part: u8,
+    input: String,
+    params: Params,
+    timeout: Option<Duration>,
+) -> Result<Solution, SolveError> {
+    // fail early instead of spawning a thread for nothing
+    if !is_known_puzzle(day, part) {
+        return Err(SolveError::UnknownPuzzle { day, part });
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let running = RunningSolution::start();
+    thread::spawn(move || {
+        let _running = running;
+        let start = Instant::now();
+        let answer = solve(day, part, &input, &params);
+        // the receiver is gone if we timed out, nobody cares about the result anymore
+        let _ = sender.send(answer.map(|answer| Solution {
+            answer,
+            elapsed: start.elapsed(),
+        }));
+    });
+
+    let result = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).map_err(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => SolveError::TimedOut(timeout),
+            mpsc::RecvTimeoutError::Disconnected => SolveError::Panicked,
+        }),
+        None => receiver.recv().map_err(|_| SolveError::Panicked),
+    };
+
+    result?
+}
+
+/// Like [`solve`], but reads the input a line at a time instead of all at once, which only
+/// day1 can
+pub fn solve_streaming(
+    day: u8,
+    part: u8,
+    reader: impl BufRead,
+    params: &Params,
+) -> Result<Solution, SolveError> {
+    if !is_known_puzzle(day, part) {
+        return Err(SolveError::UnknownPuzzle { day, part });
+    }
+    if day != 1 {
+        return Err(SolveError::Failed(anyhow::anyhow!(
+            "day {day} can't be streamed, only day 1 can"
+        )));
+    }
+
+    let start = Instant::now();
+    let tally = params
+        .dial()
+        .and_then(|dial| day1::tally(reader, dial, params.dial_target))
+        .map_err(SolveError::Failed)?;
+    let answer = match part {
+        1 => tally.landings(),
+        _ => tally.visits(),
+    };
+    Ok(Solution {
+        answer: answer.to_string(),
+        elapsed: start.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let answer = solve(1, 1, "L50\nR50\nL50\n", &Params::default()).unwrap();
+        assert_eq!(answer, "2");
+
+        let params = Params {
+            num_connections: 1,
+            ..Params::default()
+        };
+        let answer = solve(8, 1, "0,0,0\n1,1,1\n5,5,5\n", &params).unwrap();
+        assert_eq!(answer, "2");
+
+        let params = Params {
+            dial_size: 10,
+            dial_start: 0,
+            ..Params::default()
+        };
+        let answer = solve(1, 2, "R25\n", &params).unwrap();
+        assert_eq!(answer, "2");
+
+        let params = Params {
+            dial_target: 5,
+            ..params
+        };
+        let answer = solve(1, 1, "R25\n", &params).unwrap();
+        assert_eq!(answer, "1");
+        let answer = solve(1, 2, "R25\n", &params).unwrap();
+        assert_eq!(answer, "3");
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        let params = Params::default();
+        assert!(matches!(
+            solve(9, 1, "", &params),
+            Err(SolveError::UnknownPuzzle { day: 9, part: 1 })
+        ));
+        assert!(matches!(
+            solve(1, 1, "X1", &params),
+            Err(SolveError::Failed(_))
+        ));
+        let small_dial = Params {
+            dial_size: 1,
+            dial_start: 0,
+            ..Params::default()
+        };
+        assert!(matches!(
+            solve(1, 1, "L1", &small_dial),
+            Err(SolveError::Failed(_))
+        ));
+        assert!(matches!(
+            solve_with_timeout(1, 3, String::new(), params, None),
+            Err(SolveError::UnknownPuzzle { day: 1, part: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_solve_streaming() {
+        let params = Params::default();
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        let solution = solve_streaming(1, 1, input.as_bytes(), &params).unwrap();
+        assert_eq!(solution.answer, "3");
+        let solution = solve_streaming(1, 2, input.as_bytes(), &params).unwrap();
+        assert_eq!(solution.answer, "6");
+
+        assert!(matches!(
+            solve_streaming(2, 1, "".as_bytes(), &params),
+            Err(SolveError::Failed(_))
+        ));
+        assert!(matches!(
+            solve_streaming(1, 3, "".as_bytes(), &params),
+            Err(SolveError::UnknownPuzzle { day: 1, part: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_solve_with_timeout() {
+        let solution =
+            solve_with_timeout(2, 1, "11-22\n".to_string(), Params::default(), None).unwrap();
+        assert_eq!(solution.answer, "33");
+
+        // a range this large takes far longer than a millisecond to walk
+        let result = solve_with_timeout(
+            2,
+            1,
+            "1-999999999999".to_string(),
+            Params::default(),
+            Some(Duration::from_millis(1)),
+        );
+        assert!(matches!(result, Err(SolveError::TimedOut(_))));
+        // still walking it
+        assert!(running_solutions() >= 1);
+    }
+}
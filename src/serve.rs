@@ -0,0 +1,412 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex, mpsc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+
+use crate::runner::{self, Params, SolveError};
+
+/// Inputs are a few kilobytes, this only bounds how much a bad client can make us buffer.
+/// The body is read as it arrives, so claiming a large length alone doesn't allocate it
+const MAX_BODY_LEN: usize = 64 * 1024 * 1024;
+/// The request line and headers together
+const MAX_HEAD_LEN: usize = 16 * 1024;
+/// How long a client may take to send the next bytes of its request or accept the response
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+/// The threads handling connections
+const NUM_WORKERS: usize = 8;
+/// Solutions that timed out keep running, past this many new requests are turned away
+/// until some finish
+const MAX_RUNNING_SOLUTIONS: usize = 32;
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: String,
+}
+
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn error(status: u16, kind: &str, message: &str) -> Self {
+        Self {
+            status,
+            body: format!(
+                r#"{{"error":{{"kind":{},"message":{}}}}}"#,
+                json_string(kind),
+                json_string(message)
+            ),
+        }
+    }
+
+    fn bad_request(message: &str) -> Self {
+        Self::error(400, "bad_request", message)
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        422 => "Unprocessable Content",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Reads a line of the request head, failing if it doesn't end before `MAX_HEAD_LEN`
+fn read_head_line(head: &mut impl BufRead, what: &str) -> anyhow::Result<String> {
+    let mut line = String::new();
+    head.read_line(&mut line)
+        .with_context(|| format!("failed to read {what}"))?;
+    if !line.ends_with('\n') {
+        anyhow::bail!("{what} didn't end before the limit of {MAX_HEAD_LEN} bytes for the head");
+    }
+    Ok(line)
+}
+
+fn read_request(stream: impl Read) -> anyhow::Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut head = (&mut reader).take(MAX_HEAD_LEN as u64);
+
+    let request_line = read_head_line(&mut head, "request line")?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().context("no method")?.to_string();
+    let target = parts.next().context("no request target")?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|x| !x.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_string(), value.to_string())
+        })
+        .collect();
+
+    let mut content_length = 0;
+    loop {
+        let header = read_head_line(&mut head, "header")?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header
+            .split_once(':')
+            .with_context(|| format!("invalid header: {header}"))?;
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .trim()
+                .parse()
+                .context("failed to parse content length")?;
+        }
+    }
+    if content_length > MAX_BODY_LEN {
+        anyhow::bail!("body of {content_length} bytes is too large");
+    }
+
+    let mut body = Vec::new();
+    reader
+        .take(content_length as u64)
+        .read_to_end(&mut body)
+        .context("failed to read body")?;
+    if body.len() < content_length {
+        anyhow::bail!(
+            "body ended after {} of its {content_length} bytes",
+            body.len()
+        );
+    }
+    let body = String::from_utf8(body).context("body is not utf-8")?;
+
+    Ok(Request {
+        method,
+        path: path.to_string(),
+        query,
+        body,
+    })
+}
+
+fn write_response(mut stream: impl Write, response: &Response) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason_phrase(response.status),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// Parses the `/days/{day}/parts/{part}` path
+fn parse_solve_path(path: &str) -> Option<(u8, u8)> {
+    let mut segments = path.trim_matches('/').split('/');
+    let (Some("days"), Some(day), Some("parts"), Some(part), None) = (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) else {
+        return None;
+    };
+
+    Some((day.parse().ok()?, part.parse().ok()?))
+}
+
+fn handle_request(request: Request, default_timeout: Option<Duration>) -> Response {
+    let start = Instant::now();
+    let Some((day, part)) = parse_solve_path(&request.path) else {
+        return Response::error(404, "not_found", &format!("no route for {}", request.path));
+    };
+    if request.method != "POST" {
+        return Response::error(
+            405,
+            "method_not_allowed",
+            &format!("{} is not allowed, use POST", request.method),
+        );
+    }
+
+    let mut params = Params::default();
+    let mut timeout = default_timeout;
+    for (key, value) in &request.query {
+        match key.as_str() {
+            "num_connections" => match value.parse() {
+                Ok(num_connections) => params.num_connections = num_connections,
+                Err(_) => return Response::bad_request("num_connections must be a number"),
+            },
+            "dial_size" => match value.parse() {
+                Ok(dial_size) => params.dial_size = dial_size,
+                Err(_) => return Response::bad_request("dial_size must be a number"),
+            },
+            "dial_start" => match value.parse() {
+                Ok(dial_start) => params.dial_start = dial_start,
+                Err(_) => return Response::bad_request("dial_start must be a number"),
+            },
+            "dial_target" => match value.parse() {
+                Ok(dial_target) => params.dial_target = dial_target,
+                Err(_) => return Response::bad_request("dial_target must be a number"),
+            },
+            "timeout_ms" => match value.parse() {
+                Ok(timeout_ms) => timeout = Some(Duration::from_millis(timeout_ms)),
+                Err(_) => return Response::bad_request("timeout_ms must be a number"),
+            },
+            other => return Response::bad_request(&format!("unknown parameter: {other}")),
+        }
+    }
+
+    if runner::running_solutions() >= MAX_RUNNING_SOLUTIONS {
+        return Response::error(
+            503,
+            "busy",
+            "too many solutions are still running, try again later",
+        );
+    }
+    match runner::solve_with_timeout(day, part, request.body, params, timeout) {
+        Ok(solution) => Response {
+            status: 200,
+            body: format!(
+                r#"{{"day":{day},"part":{part},"answer":{},"timings":{{"solve_us":{},"total_us":{}}}}}"#,
+                json_string(&solution.answer),
+                solution.elapsed.as_micros(),
+                start.elapsed().as_micros()
+            ),
+        },
+        Err(e) => {
+            let status = match e {
+                SolveError::UnknownPuzzle { .. } => 404,
+                SolveError::TimedOut(_) => 504,
+                SolveError::Panicked => 500,
+                SolveError::Failed(_) => 422,
+            };
+            Response::error(status, e.kind(), &e.to_string())
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, default_timeout: Option<Duration>) -> anyhow::Result<()> {
+    stream
+        .set_read_timeout(Some(IO_TIMEOUT))
+        .and_then(|()| stream.set_write_timeout(Some(IO_TIMEOUT)))
+        .context("failed to set timeouts")?;
+    let response = match read_request(&stream) {
+        Ok(request) => handle_request(request, default_timeout),
+        Err(e) => Response::bad_request(&format!("{e:#}")),
+    };
+    write_response(&stream, &response).context("failed to write response")
+}
+
+/// Serves solutions over HTTP until accepting a connection fails.
+///
+/// Connections are handled by a fixed pool of `NUM_WORKERS` threads, a single request each.
+pub fn serve(listener: TcpListener, default_timeout: Option<Duration>) -> anyhow::Result<()> {
+    // no buffer, so only one accepted connection waits for a free worker, the rest wait in the
+    // listen backlog
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(0);
+    let receiver = Arc::new(Mutex::new(receiver));
+    for _ in 0..NUM_WORKERS {
+        let receiver = Arc::clone(&receiver);
+        thread::spawn(move || {
+            loop {
+                // only locked while waiting for the next connection
+                let Ok(stream) = receiver.lock().unwrap().recv() else {
+                    return;
+                };
+                if let Err(e) = handle_connection(stream, default_timeout) {
+                    eprintln!("failed to handle connection: {e:#}");
+                }
+            }
+        });
+    }
+
+    for stream in listener.incoming() {
+        let stream = stream.context("failed to accept connection")?;
+        sender.send(stream).context("every worker thread stopped")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+
+    fn start_server(default_timeout: Option<Duration>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, default_timeout));
+        addr
+    }
+
+    fn request(addr: SocketAddr, method: &str, target: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{method} {target} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, body.to_string())
+    }
+
+    #[test]
+    fn test_solve_request() {
+        let addr = start_server(None);
+        let (status, body) = request(addr, "POST", "/days/1/parts/2", "L68\nL30\nR48\n");
+        assert_eq!(status, 200);
+        assert!(body.starts_with(r#"{"day":1,"part":2,"answer":"2","timings":{"#));
+
+        let input = "0,0,0\n1,1,1\n5,5,5\n";
+        let (status, body) = request(addr, "POST", "/days/8/parts/1?num_connections=1", input);
+        assert_eq!(status, 200);
+        assert!(body.contains(r#""answer":"2""#));
+    }
+
+    #[test]
+    fn test_error_responses() {
+        let addr = start_server(None);
+
+        let (status, body) = request(addr, "POST", "/days/1/parts/1", "X1");
+        assert_eq!(status, 422);
+        assert_eq!(
+            body,
+            r#"{"error":{"kind":"failed","message":"failed to parse rotations: line 1, column 1: expected 'L' or 'R', found 'X'"}}"#
+        );
+
+        let target = "/days/2/parts/1?timeout_ms=1";
+        let (status, body) = request(addr, "POST", target, "1-999999999999");
+        assert_eq!(status, 504);
+        assert!(body.contains(r#""kind":"timed_out""#));
+
+        let (status, _) = request(addr, "POST", "/days/9/parts/1", "");
+        assert_eq!(status, 404);
+        let (status, _) = request(addr, "GET", "/days/1/parts/1", "");
+        assert_eq!(status, 405);
+        let (status, _) = request(addr, "POST", "/days/1", "");
+        assert_eq!(status, 404);
+        let (status, body) = request(addr, "POST", "/days/1/parts/1?foo=1", "");
+        assert_eq!(status, 400);
+        assert_eq!(
+            body,
+            r#"{"error":{"kind":"bad_request","message":"unknown parameter: foo"}}"#
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let addr = start_server(None);
+
+        // a header that never ends, exactly as long as the server reads before giving up
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let request_line = "POST /days/1/parts/1 HTTP/1.1\r\n";
+        let header = format!("X-Long: {}", "a".repeat(MAX_HEAD_LEN));
+        let head = format!("{request_line}{header}");
+        stream.write_all(&head.as_bytes()[..MAX_HEAD_LEN]).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(
+            response.starts_with("HTTP/1.1 400 Bad Request\r\n"),
+            "{response}"
+        );
+        assert!(response.contains("header didn't end before the limit of 16384 bytes"));
+
+        // a body shorter than its length
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST /days/1/parts/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\nL1",
+            MAX_BODY_LEN
+        )
+        .unwrap();
+        stream.shutdown(std::net::Shutdown::Write).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(
+            response.contains("body ended after 2 of its 67108864 bytes"),
+            "{response}"
+        );
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), r#""a\"b\\c\nd\u0001""#);
+    }
+}